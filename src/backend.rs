@@ -0,0 +1,47 @@
+//! The `CalendarBackend` trait implemented by every non-legacy provider.
+//!
+//! [`crate::google::CalendarClient`] and [`crate::caldav::CalDavClient`]
+//! both implement this so [`crate::CalendarService`] can dispatch without
+//! caring which one is active. The legacy [`crate::python_cli::PythonCli`]
+//! fallback predates this trait and isn't async, so it stays a separate
+//! `Backend::Python` arm in `main.rs` rather than implementing it.
+
+use crate::model::Event;
+use anyhow::Result;
+use async_trait::async_trait;
+use fgp_daemon::service::HealthStatus;
+use std::collections::HashMap;
+
+#[async_trait]
+pub trait CalendarBackend: Send + Sync {
+    /// List events in `[time_min, time_max]` (RFC3339), optionally filtered
+    /// by a free-text `query`, capped at `max_results`.
+    async fn list_events(
+        &self,
+        time_min: &str,
+        time_max: &str,
+        query: Option<&str>,
+        max_results: u64,
+    ) -> Result<Vec<Event>>;
+
+    /// Create a new event, returning it as populated by the backend (e.g.
+    /// with a server-assigned `id`).
+    async fn insert_event(&self, event: &Event) -> Result<Event>;
+
+    /// Fetch a single event by id, e.g. so `calendar.update` can merge
+    /// partial changes onto the current fields.
+    async fn get_event(&self, id: &str) -> Result<Event>;
+
+    /// Replace the event at `id` with `event`'s fields.
+    async fn update_event(&self, id: &str, event: &Event) -> Result<Event>;
+
+    /// Delete the event at `id`.
+    async fn delete_event(&self, id: &str) -> Result<()>;
+
+    /// Short identifier reported in `health_check`'s `backend` entry.
+    fn name(&self) -> &'static str;
+
+    /// Backend-specific health signals (e.g. whether credentials are
+    /// present), merged into the service's overall `health_check` output.
+    fn health(&self) -> HashMap<String, HealthStatus>;
+}
@@ -0,0 +1,35 @@
+//! CalDAV credential loading.
+//!
+//! Unlike Google, most self-hosted CalDAV servers (Nextcloud, Radicale, ...)
+//! are authenticated with a per-device app password over plain HTTP basic
+//! auth, so there's no token refresh dance here — just read the three
+//! fields once at startup.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::path::PathBuf;
+
+/// Credentials for a CalDAV server, read from
+/// `~/.fgp/auth/caldav/credentials.json`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CaldavCredentials {
+    /// Base URL of the user's calendar collection, e.g.
+    /// `https://cloud.example.com/remote.php/dav/calendars/alice/personal/`.
+    pub caldav_base_url: String,
+    pub username: String,
+    pub app_password: String,
+}
+
+impl CaldavCredentials {
+    pub fn load() -> Result<Self> {
+        let path = credentials_path()?;
+        let raw = std::fs::read_to_string(&path)
+            .with_context(|| format!("failed to read CalDAV credentials at {}", path.display()))?;
+        serde_json::from_str(&raw).with_context(|| format!("invalid credentials JSON at {}", path.display()))
+    }
+}
+
+pub fn credentials_path() -> Result<PathBuf> {
+    let home = dirs::home_dir().context("could not determine home directory")?;
+    Ok(home.join(".fgp/auth/caldav/credentials.json"))
+}
@@ -0,0 +1,337 @@
+//! Minimal CalDAV client: REPORT for reads, PUT for writes.
+//!
+//! Talks to whatever CalDAV server the user configured (Nextcloud,
+//! Radicale, etc.) against a single calendar collection. Event bodies are
+//! generated/parsed as iCalendar text via [`crate::ical`].
+
+use super::auth::CaldavCredentials;
+use crate::backend::CalendarBackend;
+use crate::ical;
+use crate::model::Event;
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use base64::Engine;
+use bytes::Bytes;
+use fgp_daemon::service::HealthStatus;
+use http_body_util::{BodyExt, Full};
+use hyper_rustls::HttpsConnectorBuilder;
+use hyper_util::client::legacy::{connect::HttpConnector, Client};
+use hyper_util::rt::TokioExecutor;
+use std::collections::HashMap;
+
+type HttpsClient = Client<hyper_rustls::HttpsConnector<HttpConnector>, Full<Bytes>>;
+
+/// Talks to a single CalDAV calendar collection over REPORT/GET/PUT/DELETE.
+pub struct CalDavClient {
+    http: HttpsClient,
+    base_url: String,
+    auth_header: String,
+}
+
+impl CalDavClient {
+    /// Build a client, loading the base URL and app-password credentials
+    /// from `~/.fgp/auth/caldav/credentials.json`.
+    pub fn new() -> Result<Self> {
+        let creds = CaldavCredentials::load()?;
+        let https = HttpsConnectorBuilder::new()
+            .with_native_roots()
+            .context("loading native TLS roots")?
+            .https_only()
+            .enable_http1()
+            .build();
+        let http = Client::builder(TokioExecutor::new()).build(https);
+        let auth_header = format!(
+            "Basic {}",
+            base64::engine::general_purpose::STANDARD
+                .encode(format!("{}:{}", creds.username, creds.app_password))
+        );
+        let base_url = if creds.caldav_base_url.ends_with('/') {
+            creds.caldav_base_url
+        } else {
+            format!("{}/", creds.caldav_base_url)
+        };
+        Ok(Self {
+            http,
+            base_url,
+            auth_header,
+        })
+    }
+
+    async fn request(&self, method: &str, url: &str, body: String) -> Result<Bytes> {
+        let req = hyper::Request::builder()
+            .method(method)
+            .uri(url)
+            .header("authorization", &self.auth_header)
+            .header("content-type", "application/xml; charset=utf-8")
+            .header("depth", "1")
+            .body(Full::new(Bytes::from(body)))
+            .context("building CalDAV request")?;
+
+        let resp = self
+            .http
+            .request(req)
+            .await
+            .with_context(|| format!("requesting {url}"))?;
+
+        let status = resp.status();
+        let bytes = resp
+            .into_body()
+            .collect()
+            .await
+            .context("reading CalDAV response body")?
+            .to_bytes();
+
+        if !status.is_success() {
+            let text = String::from_utf8_lossy(&bytes);
+            bail!("CalDAV error ({status}): {text}");
+        }
+
+        Ok(bytes)
+    }
+}
+
+#[async_trait]
+impl CalendarBackend for CalDavClient {
+    /// Issue a `REPORT` calendar-query with a `VEVENT` time-range filter.
+    /// `query` isn't supported server-side by plain CalDAV time-range
+    /// filters, so it's applied client-side against the summary.
+    async fn list_events(
+        &self,
+        time_min: &str,
+        time_max: &str,
+        query: Option<&str>,
+        max_results: u64,
+    ) -> Result<Vec<Event>> {
+        let body = format!(
+            r#"<?xml version="1.0" encoding="utf-8" ?>
+<c:calendar-query xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+  <d:prop>
+    <d:getetag/>
+    <c:calendar-data/>
+  </d:prop>
+  <c:filter>
+    <c:comp-filter name="VCALENDAR">
+      <c:comp-filter name="VEVENT">
+        <c:time-range start="{}" end="{}"/>
+      </c:comp-filter>
+    </c:comp-filter>
+  </c:filter>
+</c:calendar-query>"#,
+            to_ical_utc(time_min)?,
+            to_ical_utc(time_max)?
+        );
+
+        let bytes = self.request("REPORT", &self.base_url, body).await?;
+        let xml = String::from_utf8_lossy(&bytes);
+
+        let mut events = Vec::new();
+        for ics in extract_tag_bodies(&xml, "calendar-data") {
+            events.extend(ical::parse_vevents(&unescape_xml(&ics)));
+        }
+
+        if let Some(q) = query {
+            let needle = q.to_lowercase();
+            events.retain(|e| e.summary.to_lowercase().contains(&needle));
+        }
+        events.truncate(max_results as usize);
+        Ok(events)
+    }
+
+    /// PUT a generated iCalendar VEVENT into the collection.
+    async fn insert_event(&self, event: &Event) -> Result<Event> {
+        let uid = event.id.clone().unwrap_or_else(ical::new_uid);
+        let ics = ical::build_vevent(&uid, event);
+        let url = format!("{}{}.ics", self.base_url, uid);
+
+        let req = hyper::Request::builder()
+            .method("PUT")
+            .uri(&url)
+            .header("authorization", &self.auth_header)
+            .header("content-type", "text/calendar; charset=utf-8")
+            .body(Full::new(Bytes::from(ics)))
+            .context("building CalDAV PUT request")?;
+
+        let resp = self
+            .http
+            .request(req)
+            .await
+            .with_context(|| format!("PUT {url}"))?;
+        if !resp.status().is_success() {
+            bail!("CalDAV PUT failed ({})", resp.status());
+        }
+
+        let mut created = event.clone();
+        created.id = Some(uid);
+        Ok(created)
+    }
+
+    /// `GET` the `.ics` resource for `id` and parse its single `VEVENT`.
+    async fn get_event(&self, id: &str) -> Result<Event> {
+        let url = format!("{}{}.ics", self.base_url, id);
+        let req = hyper::Request::builder()
+            .method("GET")
+            .uri(&url)
+            .header("authorization", &self.auth_header)
+            .body(Full::new(Bytes::new()))
+            .context("building CalDAV GET request")?;
+
+        let resp = self
+            .http
+            .request(req)
+            .await
+            .with_context(|| format!("GET {url}"))?;
+        let status = resp.status();
+        let bytes = resp
+            .into_body()
+            .collect()
+            .await
+            .context("reading CalDAV response body")?
+            .to_bytes();
+        if !status.is_success() {
+            bail!("CalDAV GET failed ({status})");
+        }
+
+        ical::parse_vevents(&String::from_utf8_lossy(&bytes))
+            .into_iter()
+            .next()
+            .with_context(|| format!("no VEVENT found in {url}"))
+    }
+
+    /// Overwrite the `.ics` resource at `id` with `event`'s fields.
+    async fn update_event(&self, id: &str, event: &Event) -> Result<Event> {
+        let ics = ical::build_vevent(id, event);
+        let url = format!("{}{}.ics", self.base_url, id);
+
+        let req = hyper::Request::builder()
+            .method("PUT")
+            .uri(&url)
+            .header("authorization", &self.auth_header)
+            .header("content-type", "text/calendar; charset=utf-8")
+            .body(Full::new(Bytes::from(ics)))
+            .context("building CalDAV PUT request")?;
+
+        let resp = self
+            .http
+            .request(req)
+            .await
+            .with_context(|| format!("PUT {url}"))?;
+        if !resp.status().is_success() {
+            bail!("CalDAV PUT failed ({})", resp.status());
+        }
+
+        let mut updated = event.clone();
+        updated.id = Some(id.to_string());
+        Ok(updated)
+    }
+
+    /// `DELETE` the `.ics` resource at `id`.
+    async fn delete_event(&self, id: &str) -> Result<()> {
+        let url = format!("{}{}.ics", self.base_url, id);
+        let req = hyper::Request::builder()
+            .method("DELETE")
+            .uri(&url)
+            .header("authorization", &self.auth_header)
+            .body(Full::new(Bytes::new()))
+            .context("building CalDAV DELETE request")?;
+
+        let resp = self
+            .http
+            .request(req)
+            .await
+            .with_context(|| format!("DELETE {url}"))?;
+        if !resp.status().is_success() {
+            bail!("CalDAV DELETE failed ({})", resp.status());
+        }
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "caldav"
+    }
+
+    fn health(&self) -> HashMap<String, HealthStatus> {
+        let mut status = HashMap::new();
+        let ok = super::auth::credentials_path()
+            .map(|p| p.exists())
+            .unwrap_or(false);
+        status.insert(
+            "caldav_credentials".into(),
+            HealthStatus {
+                ok,
+                latency_ms: None,
+                message: Some(if ok {
+                    "credentials.json found".into()
+                } else {
+                    "~/.fgp/auth/caldav/credentials.json missing".into()
+                }),
+            },
+        );
+        status
+    }
+}
+
+/// Convert an RFC3339 timestamp into the basic iCalendar UTC form
+/// (`YYYYMMDDTHHMMSSZ`) used in CalDAV time-range filters.
+fn to_ical_utc(rfc3339: &str) -> Result<String> {
+    let dt = chrono::DateTime::parse_from_rfc3339(rfc3339)
+        .with_context(|| format!("invalid RFC3339 timestamp: {rfc3339}"))?;
+    Ok(dt
+        .with_timezone(&chrono::Utc)
+        .format("%Y%m%dT%H%M%SZ")
+        .to_string())
+}
+
+/// Pull the text content of every `<prefix:tag>...</prefix:tag>` element
+/// out of a multistatus response, ignoring the namespace prefix.
+fn extract_tag_bodies(xml: &str, tag: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut rest = xml;
+    loop {
+        let Some(open_start) = find_tag_open(rest, tag) else {
+            break;
+        };
+        let Some(open_end) = rest[open_start..].find('>') else {
+            break;
+        };
+        let content_start = open_start + open_end + 1;
+        let Some(close_rel) = rest[content_start..].find(&format!(":{tag}>")) else {
+            break;
+        };
+        // Back up to the start of the closing tag's "</prefix:" part.
+        let close_tag_start = rest[..content_start + close_rel]
+            .rfind("</")
+            .unwrap_or(content_start + close_rel);
+        out.push(rest[content_start..close_tag_start].to_string());
+        // Resume after the closing tag's ":tag>".
+        rest = &rest[content_start + close_rel + tag.len() + 2..];
+    }
+    out
+}
+
+fn find_tag_open(xml: &str, tag: &str) -> Option<usize> {
+    // Matches "<tag" or "<ns:tag" (not the closing "</...").
+    let mut idx = 0;
+    while let Some(pos) = xml[idx..].find(&format!(":{tag}")).or_else(|| {
+        if idx == 0 {
+            xml.find(&format!("<{tag}"))
+        } else {
+            None
+        }
+    }) {
+        let abs = idx + pos;
+        let tag_start = xml[..=abs].rfind('<')?;
+        if !xml[tag_start..].starts_with("</") {
+            return Some(tag_start);
+        }
+        idx = abs + 1;
+    }
+    None
+}
+
+fn unescape_xml(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+}
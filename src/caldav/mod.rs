@@ -0,0 +1,7 @@
+//! CalDAV backend, for self-hosted calendars (Nextcloud, Radicale, ...)
+//! that aren't Google Calendar.
+
+pub mod auth;
+pub mod client;
+
+pub use client::CalDavClient;
@@ -0,0 +1,163 @@
+//! OAuth credential loading and token refresh for the Calendar v3 API.
+//!
+//! Credentials are read from `~/.fgp/auth/google/credentials.json` (the
+//! client id/secret obtained from Google Cloud console) and the refreshed
+//! access token is cached alongside it in `token.json` so we don't hit the
+//! token endpoint on every call.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const TOKEN_URI: &str = "https://oauth2.googleapis.com/token";
+
+/// Client id/secret and refresh token, as saved by the initial OAuth flow.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Credentials {
+    pub client_id: String,
+    pub client_secret: String,
+    pub refresh_token: String,
+}
+
+/// Cached access token plus its expiry, persisted between daemon restarts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedToken {
+    access_token: String,
+    /// Unix timestamp (seconds) after which the token should be refreshed.
+    expires_at: u64,
+}
+
+/// Holds credentials and the current access token, refreshing as needed.
+pub struct TokenStore {
+    credentials: Credentials,
+    token_path: PathBuf,
+    cached: std::sync::Mutex<Option<CachedToken>>,
+}
+
+impl TokenStore {
+    /// Load credentials from `~/.fgp/auth/google/credentials.json`.
+    pub fn load() -> Result<Self> {
+        let auth_dir = auth_dir()?;
+        let creds_path = auth_dir.join("credentials.json");
+        let raw = std::fs::read_to_string(&creds_path).with_context(|| {
+            format!(
+                "failed to read Google OAuth credentials at {}",
+                creds_path.display()
+            )
+        })?;
+        let credentials: Credentials = serde_json::from_str(&raw)
+            .with_context(|| format!("invalid credentials JSON at {}", creds_path.display()))?;
+
+        let token_path = auth_dir.join("token.json");
+        let cached = std::fs::read_to_string(&token_path)
+            .ok()
+            .and_then(|raw| serde_json::from_str(&raw).ok());
+
+        Ok(Self {
+            credentials,
+            token_path,
+            cached: std::sync::Mutex::new(cached),
+        })
+    }
+
+    /// Return a valid access token, refreshing it against Google's token
+    /// endpoint first if it's missing or within 60 seconds of expiring.
+    pub async fn access_token(&self, http: &hyper_util::client::legacy::Client<hyper_rustls::HttpsConnector<hyper_util::client::legacy::connect::HttpConnector>, http_body_util::Full<bytes::Bytes>>) -> Result<String> {
+        let now = now_secs();
+        {
+            let cached = self.cached.lock().unwrap();
+            if let Some(token) = cached.as_ref() {
+                if token.expires_at > now + 60 {
+                    return Ok(token.access_token.clone());
+                }
+            }
+        }
+        self.refresh(http, now).await
+    }
+
+    async fn refresh(
+        &self,
+        http: &hyper_util::client::legacy::Client<hyper_rustls::HttpsConnector<hyper_util::client::legacy::connect::HttpConnector>, http_body_util::Full<bytes::Bytes>>,
+        now: u64,
+    ) -> Result<String> {
+        #[derive(Serialize)]
+        struct RefreshRequest<'a> {
+            client_id: &'a str,
+            client_secret: &'a str,
+            refresh_token: &'a str,
+            grant_type: &'a str,
+        }
+        #[derive(Deserialize)]
+        struct RefreshResponse {
+            access_token: String,
+            expires_in: u64,
+        }
+
+        let body = serde_json::to_vec(&RefreshRequest {
+            client_id: &self.credentials.client_id,
+            client_secret: &self.credentials.client_secret,
+            refresh_token: &self.credentials.refresh_token,
+            grant_type: "refresh_token",
+        })?;
+
+        let req = hyper::Request::builder()
+            .method(hyper::Method::POST)
+            .uri(TOKEN_URI)
+            .header("content-type", "application/json")
+            .body(http_body_util::Full::new(bytes::Bytes::from(body)))
+            .context("building token refresh request")?;
+
+        let resp = http
+            .request(req)
+            .await
+            .context("requesting Google OAuth token refresh")?;
+
+        if !resp.status().is_success() {
+            bail!("Google OAuth token refresh failed: {}", resp.status());
+        }
+
+        let bytes = http_body_util::BodyExt::collect(resp.into_body())
+            .await
+            .context("reading token refresh response")?
+            .to_bytes();
+        let parsed: RefreshResponse =
+            serde_json::from_slice(&bytes).context("parsing token refresh response")?;
+
+        let token = CachedToken {
+            access_token: parsed.access_token.clone(),
+            expires_at: now + parsed.expires_in,
+        };
+        self.persist(&token)?;
+        *self.cached.lock().unwrap() = Some(token);
+
+        Ok(parsed.access_token)
+    }
+
+    fn persist(&self, token: &CachedToken) -> Result<()> {
+        let raw = serde_json::to_string_pretty(token)?;
+        std::fs::write(&self.token_path, raw)
+            .with_context(|| format!("caching token at {}", self.token_path.display()))
+    }
+}
+
+fn auth_dir() -> Result<PathBuf> {
+    let home = dirs::home_dir().context("could not determine home directory")?;
+    Ok(home.join(".fgp/auth/google"))
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Returns the expected credentials path, for health-check reporting.
+pub fn credentials_path() -> Result<PathBuf> {
+    Ok(auth_dir()?.join("credentials.json"))
+}
+
+pub fn credentials_exist(path: &Path) -> bool {
+    path.exists()
+}
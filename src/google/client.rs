@@ -0,0 +1,218 @@
+//! Minimal async Google Calendar v3 client over hyper + rustls.
+//!
+//! This intentionally only implements the handful of endpoints the daemon
+//! needs (list/insert/get/update/delete), modeled loosely on the shape of generated
+//! clients like `async-google-apis` but hand-written so we don't pull in a
+//! full API surface we'll never call.
+
+use super::auth::TokenStore;
+use super::types::EventListResponse;
+use crate::backend::CalendarBackend;
+use crate::model::Event;
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use bytes::Bytes;
+use fgp_daemon::service::HealthStatus;
+use http_body_util::{BodyExt, Full};
+use hyper_rustls::HttpsConnectorBuilder;
+use hyper_util::client::legacy::{connect::HttpConnector, Client};
+use hyper_util::rt::TokioExecutor;
+use std::collections::HashMap;
+
+const CALENDAR_API_BASE: &str = "https://www.googleapis.com/calendar/v3";
+/// We only ever operate on the user's primary calendar.
+const PRIMARY_CALENDAR: &str = "primary";
+
+type HttpsClient = Client<hyper_rustls::HttpsConnector<HttpConnector>, Full<Bytes>>;
+
+/// Talks to the Calendar v3 REST API on behalf of the primary calendar.
+pub struct CalendarClient {
+    http: HttpsClient,
+    tokens: TokenStore,
+}
+
+impl CalendarClient {
+    /// Build a client, loading OAuth credentials from `~/.fgp/auth/google/`.
+    pub fn new() -> Result<Self> {
+        let https = HttpsConnectorBuilder::new()
+            .with_native_roots()
+            .context("loading native TLS roots")?
+            .https_only()
+            .enable_http1()
+            .build();
+        let http = Client::builder(TokioExecutor::new()).build(https);
+        let tokens = TokenStore::load()?;
+        Ok(Self { http, tokens })
+    }
+
+    async fn authed_request(
+        &self,
+        method: hyper::Method,
+        url: &str,
+        body: Option<Vec<u8>>,
+    ) -> Result<Bytes> {
+        let token = self.tokens.access_token(&self.http).await?;
+        let body = body.unwrap_or_default();
+        let req = hyper::Request::builder()
+            .method(method)
+            .uri(url)
+            .header("authorization", format!("Bearer {token}"))
+            .header("content-type", "application/json")
+            .body(Full::new(Bytes::from(body)))
+            .context("building Calendar API request")?;
+
+        let resp = self
+            .http
+            .request(req)
+            .await
+            .with_context(|| format!("requesting {url}"))?;
+
+        let status = resp.status();
+        let bytes = resp
+            .into_body()
+            .collect()
+            .await
+            .context("reading Calendar API response body")?
+            .to_bytes();
+
+        if !status.is_success() {
+            let text = String::from_utf8_lossy(&bytes);
+            bail!("Calendar API error ({status}): {text}");
+        }
+
+        Ok(bytes)
+    }
+
+}
+
+#[async_trait]
+impl CalendarBackend for CalendarClient {
+    /// List events in `[time_min, time_max]`, optionally filtered by a free
+    /// text `query`, following pagination until exhausted or until
+    /// `max_results` events have been collected.
+    async fn list_events(
+        &self,
+        time_min: &str,
+        time_max: &str,
+        query: Option<&str>,
+        max_results: u64,
+    ) -> Result<Vec<Event>> {
+        let mut events = Vec::new();
+        let mut page_token: Option<String> = None;
+
+        loop {
+            // `orderBy=startTime` is only valid alongside `singleEvents=true`;
+            // we need `singleEvents=false` so recurring masters come back
+            // for `rrule::expand` to expand, so ordering is left to
+            // whatever Google returns and sorted out client-side below.
+            let mut url = format!(
+                "{CALENDAR_API_BASE}/calendars/{PRIMARY_CALENDAR}/events\
+                 ?timeMin={}&timeMax={}&singleEvents=false&maxResults={}",
+                urlencoding_lite(time_min),
+                urlencoding_lite(time_max),
+                max_results.min(2500)
+            );
+            if let Some(q) = query {
+                url.push_str("&q=");
+                url.push_str(&urlencoding_lite(q));
+            }
+            if let Some(token) = &page_token {
+                url.push_str("&pageToken=");
+                url.push_str(token);
+            }
+
+            let bytes = self.authed_request(hyper::Method::GET, &url, None).await?;
+            let mut page: EventListResponse =
+                serde_json::from_slice(&bytes).context("parsing events.list response")?;
+            events.append(&mut page.items);
+
+            if events.len() as u64 >= max_results {
+                events.truncate(max_results as usize);
+                break;
+            }
+            match page.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+
+        events.sort_by_key(|e| e.start.to_local().ok());
+        Ok(events)
+    }
+
+    /// Insert a new event onto the primary calendar, returning it as
+    /// populated by the API (with `id`/`htmlLink` filled in).
+    async fn insert_event(&self, event: &Event) -> Result<Event> {
+        let url = format!("{CALENDAR_API_BASE}/calendars/{PRIMARY_CALENDAR}/events");
+        let body = serde_json::to_vec(event).context("serializing event")?;
+        let bytes = self
+            .authed_request(hyper::Method::POST, &url, Some(body))
+            .await?;
+        serde_json::from_slice(&bytes).context("parsing events.insert response")
+    }
+
+    /// Fetch a single event by id from the primary calendar.
+    async fn get_event(&self, id: &str) -> Result<Event> {
+        let url = format!("{CALENDAR_API_BASE}/calendars/{PRIMARY_CALENDAR}/events/{id}");
+        let bytes = self.authed_request(hyper::Method::GET, &url, None).await?;
+        serde_json::from_slice(&bytes).context("parsing events.get response")
+    }
+
+    /// Replace the event at `id`, returning it as populated by the API.
+    async fn update_event(&self, id: &str, event: &Event) -> Result<Event> {
+        let url = format!("{CALENDAR_API_BASE}/calendars/{PRIMARY_CALENDAR}/events/{id}");
+        let body = serde_json::to_vec(event).context("serializing event")?;
+        let bytes = self
+            .authed_request(hyper::Method::PUT, &url, Some(body))
+            .await?;
+        serde_json::from_slice(&bytes).context("parsing events.update response")
+    }
+
+    /// Delete the event at `id` from the primary calendar.
+    async fn delete_event(&self, id: &str) -> Result<()> {
+        let url = format!("{CALENDAR_API_BASE}/calendars/{PRIMARY_CALENDAR}/events/{id}");
+        self.authed_request(hyper::Method::DELETE, &url, None)
+            .await?;
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "google"
+    }
+
+    fn health(&self) -> HashMap<String, HealthStatus> {
+        let mut status = HashMap::new();
+        let ok = super::auth::credentials_path()
+            .map(|p| super::auth::credentials_exist(&p))
+            .unwrap_or(false);
+        status.insert(
+            "google_credentials".into(),
+            HealthStatus {
+                ok,
+                latency_ms: None,
+                message: Some(if ok {
+                    "credentials.json found".into()
+                } else {
+                    "~/.fgp/auth/google/credentials.json missing".into()
+                }),
+            },
+        );
+        status
+    }
+}
+
+/// Percent-encode just enough of a query string for a URL query parameter.
+/// Not a general-purpose encoder; callers only ever pass free-text search
+/// terms and RFC3339 timestamps here.
+fn urlencoding_lite(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for byte in s.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(byte as char)
+            }
+            _ => out.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    out
+}
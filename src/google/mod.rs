@@ -0,0 +1,10 @@
+//! Native Google Calendar v3 backend.
+//!
+//! Replaces the old `calendar-cli.py` shell-out: we talk to the REST API
+//! directly over hyper+rustls and manage OAuth token refresh ourselves.
+
+pub mod auth;
+pub mod client;
+pub mod types;
+
+pub use client::CalendarClient;
@@ -0,0 +1,18 @@
+//! JSON shapes for the Google Calendar v3 REST API.
+//!
+//! [`Event`]/[`EventDateTime`] live in [`crate::model`] since they're shared
+//! with the CalDAV backend; this module only adds the response envelope
+//! that's specific to the `events.list` endpoint
+//! (<https://developers.google.com/calendar/api/v3/reference/events>).
+
+use crate::model::Event;
+use serde::Deserialize;
+
+/// Response envelope for `events.list`.
+#[derive(Debug, Deserialize)]
+pub struct EventListResponse {
+    #[serde(default)]
+    pub items: Vec<Event>,
+    #[serde(rename = "nextPageToken")]
+    pub next_page_token: Option<String>,
+}
@@ -0,0 +1,339 @@
+//! iCalendar (RFC 5545) VEVENT encoding/decoding, shared by the CalDAV
+//! backend and the `calendar.create`/`import`/`export` methods.
+
+use crate::model::{Event, EventDateTime};
+use anyhow::{bail, Context, Result};
+use chrono::TimeZone;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Generate a stable event UID, suitable for both the `UID` VEVENT
+/// property and (since CalDAV addresses events by filename) the `.ics`
+/// resource name.
+pub fn new_uid() -> String {
+    format!("{}@fast-gateway-protocol", Uuid::new_v4())
+}
+
+/// Derive a Google-safe event id from an arbitrary `UID`.
+///
+/// Google's `events.insert` rejects any client-supplied `id` that isn't
+/// 5-1024 lowercase base32hex characters (`a`-`v`, `0`-`9`); our own
+/// `UID`s (and ICS `UID`s from other apps) contain `@`, `-`, and mixed
+/// case. Hashing into a v5 UUID and taking its simple (no-hyphen) hex
+/// form is deterministic, so the same `UID` always maps to the same
+/// Google id and re-imports stay idempotent.
+pub fn google_safe_id(uid: &str) -> String {
+    Uuid::new_v5(&Uuid::NAMESPACE_URL, uid.as_bytes())
+        .as_simple()
+        .to_string()
+}
+
+/// Parse a `start`/`end` parameter as RFC3339, or as a bare `YYYY-MM-DD`
+/// all-day date, returning a structured error instead of forwarding
+/// whatever garbage was passed in.
+pub fn parse_boundary(s: &str, time_zone: Option<&str>) -> Result<EventDateTime> {
+    if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(s) {
+        return Ok(EventDateTime {
+            date_time: Some(dt.to_rfc3339_opts(chrono::SecondsFormat::Secs, false)),
+            date: None,
+            time_zone: time_zone.map(str::to_string),
+        });
+    }
+    if chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").is_ok() {
+        return Ok(EventDateTime {
+            date_time: None,
+            date: Some(s.to_string()),
+            time_zone: None,
+        });
+    }
+    bail!("invalid date/time '{s}': expected RFC3339 (e.g. 2026-08-15T09:00:00-07:00) or an all-day date (e.g. 2026-08-15)")
+}
+
+/// Validate that `tz` is a recognized IANA time zone name.
+pub fn validate_timezone(tz: &str) -> Result<()> {
+    tz.parse::<chrono_tz::Tz>()
+        .map(|_| ())
+        .map_err(|_| anyhow::anyhow!("unknown IANA time zone: {tz}"))
+}
+
+/// The system's local IANA time zone, used as the default when `create`
+/// isn't given an explicit `timezone` param.
+pub fn system_timezone() -> String {
+    iana_time_zone::get_timezone().unwrap_or_else(|_| "UTC".to_string())
+}
+
+/// Build a full `VCALENDAR`/`VEVENT` document for a single `event`,
+/// stamping `DTSTAMP` at generation time.
+pub fn build_vevent(uid: &str, event: &Event) -> String {
+    build_vcalendar_from_lines(event_lines(uid, event))
+}
+
+/// Build a single `VCALENDAR` document containing one `VEVENT` per entry
+/// in `events`, e.g. for `calendar.export`. Events missing a `UID` get one
+/// generated so the document is always well-formed.
+pub fn build_vcalendar(events: &[Event]) -> String {
+    let mut lines = Vec::new();
+    for event in events {
+        let uid = event.id.clone().unwrap_or_else(new_uid);
+        lines.extend(event_lines(&uid, event));
+    }
+    build_vcalendar_from_lines(lines)
+}
+
+fn build_vcalendar_from_lines(event_lines: Vec<String>) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//fast-gateway-protocol//calendar//EN".to_string(),
+    ];
+    lines.extend(event_lines);
+    lines.push("END:VCALENDAR".to_string());
+    lines
+        .iter()
+        .flat_map(|line| fold_line(line))
+        .collect::<Vec<_>>()
+        .join("\r\n")
+        + "\r\n"
+}
+
+/// The `VEVENT...END:VEVENT` lines for one event, unfolded (folding is
+/// applied once, document-wide, in [`build_vcalendar_from_lines`]).
+fn event_lines(uid: &str, event: &Event) -> Vec<String> {
+    let mut lines = vec![
+        "BEGIN:VEVENT".to_string(),
+        format!("UID:{uid}"),
+        format!("DTSTAMP:{}", chrono::Utc::now().format("%Y%m%dT%H%M%SZ")),
+        format!("SUMMARY:{}", escape_text(&event.summary)),
+        format!("DTSTART{}", event_datetime_to_ical(&event.start)),
+        format!("DTEND{}", event_datetime_to_ical(&event.end)),
+    ];
+    if let Some(desc) = &event.description {
+        lines.push(format!("DESCRIPTION:{}", escape_text(desc)));
+    }
+    if let Some(location) = &event.location {
+        lines.push(format!("LOCATION:{}", escape_text(location)));
+    }
+    if let Some(recurrence) = &event.recurrence {
+        for line in recurrence {
+            lines.push(line.clone());
+        }
+    }
+    if let Some(attendees) = &event.attendees {
+        for attendee in attendees {
+            lines.push(format!("ATTENDEE:mailto:{}", attendee.email));
+        }
+    }
+    lines.push("END:VEVENT".to_string());
+    lines
+}
+
+/// Fold a line to RFC 5545's 75-octet limit: the first chunk stands alone,
+/// every subsequent chunk is emitted as its own output line prefixed with
+/// a single space (an iCalendar continuation line).
+fn fold_line(line: &str) -> Vec<String> {
+    const LIMIT: usize = 75;
+    let bytes = line.as_bytes();
+    if bytes.len() <= LIMIT {
+        return vec![line.to_string()];
+    }
+
+    let mut out = Vec::new();
+    let mut start = 0;
+    let mut first = true;
+    while start < bytes.len() {
+        let budget = if first { LIMIT } else { LIMIT - 1 };
+        let mut end = (start + budget).min(bytes.len());
+        // Don't split a UTF-8 character across chunks.
+        while end > start && !line.is_char_boundary(end) {
+            end -= 1;
+        }
+        let chunk = &line[start..end];
+        out.push(if first {
+            chunk.to_string()
+        } else {
+            format!(" {chunk}")
+        });
+        start = end;
+        first = false;
+    }
+    out
+}
+
+/// Render an `EventDateTime` as a `DTSTART`/`DTEND` property, including its
+/// leading `:`/`;...:` so all-day and `TZID`-qualified forms fit the same
+/// call site.
+fn event_datetime_to_ical(dt: &EventDateTime) -> String {
+    if let Some(date) = &dt.date {
+        return format!(";VALUE=DATE:{}", date.replace('-', ""));
+    }
+    let date_time = dt.date_time.clone().unwrap_or_default();
+    let parsed = chrono::DateTime::parse_from_rfc3339(&date_time)
+        .unwrap_or_else(|_| chrono::DateTime::from(chrono::Utc::now()));
+
+    match &dt.time_zone {
+        Some(tz) if tz != "UTC" => {
+            format!(
+                ";TZID={tz}:{}",
+                parsed.format("%Y%m%dT%H%M%S")
+            )
+        }
+        _ => format!(":{}", parsed.with_timezone(&chrono::Utc).format("%Y%m%dT%H%M%SZ")),
+    }
+}
+
+fn escape_text(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace(',', "\\,")
+        .replace(';', "\\;")
+        .replace('\n', "\\n")
+}
+
+/// Parse every `VEVENT` block out of a (possibly multi-event) iCalendar
+/// document, unfolding continuation lines first.
+pub fn parse_vevents(ics: &str) -> Vec<Event> {
+    let lines = unfold_lines(ics);
+    let mut events = Vec::new();
+    let mut current: Option<HashMap<String, String>> = None;
+
+    for line in lines {
+        if line == "BEGIN:VEVENT" {
+            current = Some(HashMap::new());
+            continue;
+        }
+        if line == "END:VEVENT" {
+            if let Some(fields) = current.take() {
+                if let Some(event) = vevent_to_event(&fields) {
+                    events.push(event);
+                }
+            }
+            continue;
+        }
+        let Some(fields) = current.as_mut() else {
+            continue;
+        };
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        // Drop any ";PARAM=..." suffix on the key for the common fields we
+        // read; VALUE=DATE/TZID are the parameters that actually change how
+        // we interpret the value, so keep the raw key for DTSTART/DTEND.
+        let base_key = key.split(';').next().unwrap_or(key);
+        fields.insert(base_key.to_string(), value.to_string());
+        if base_key == "DTSTART" || base_key == "DTEND" {
+            fields.insert(format!("{base_key}_RAW_KEY"), key.to_string());
+        }
+    }
+
+    events
+}
+
+fn vevent_to_event(fields: &HashMap<String, String>) -> Option<Event> {
+    let summary = fields.get("SUMMARY").cloned().unwrap_or_default();
+    let start = fields.get("DTSTART")?;
+    let end = fields.get("DTEND")?;
+    Some(Event {
+        id: fields.get("UID").cloned(),
+        summary,
+        description: fields.get("DESCRIPTION").cloned(),
+        location: fields.get("LOCATION").cloned(),
+        attendees: None,
+        start: ical_value_to_event_datetime(
+            start,
+            fields.get("DTSTART_RAW_KEY").map(String::as_str),
+        ),
+        end: ical_value_to_event_datetime(end, fields.get("DTEND_RAW_KEY").map(String::as_str)),
+        recurrence: fields.get("RRULE").map(|r| vec![format!("RRULE:{r}")]),
+        recurrence_id: None,
+        html_link: None,
+    })
+}
+
+fn ical_value_to_event_datetime(value: &str, raw_key: Option<&str>) -> EventDateTime {
+    let is_all_day = raw_key.map(|k| k.contains("VALUE=DATE")).unwrap_or(false);
+    if is_all_day {
+        // YYYYMMDD -> YYYY-MM-DD
+        let date = format!("{}-{}-{}", &value[0..4], &value[4..6], &value[6..8]);
+        return EventDateTime {
+            date_time: None,
+            date: Some(date),
+            time_zone: None,
+        };
+    }
+
+    let tzid = raw_key.and_then(|k| {
+        k.split(';')
+            .find_map(|p| p.strip_prefix("TZID=").map(str::to_string))
+    });
+
+    // Basic form YYYYMMDDTHHMMSS(Z)? -> RFC3339.
+    let (naive, is_utc) = match value.strip_suffix('Z') {
+        Some(rest) => (rest, true),
+        None => (value, false),
+    };
+    if naive.len() < 15 {
+        return EventDateTime {
+            date_time: Some(value.to_string()),
+            date: None,
+            time_zone: tzid,
+        };
+    }
+    let naive_dt = chrono::NaiveDateTime::parse_from_str(naive, "%Y%m%dT%H%M%S").ok();
+
+    // A `Z`-suffixed value is already UTC. Otherwise this is either a
+    // `TZID`-qualified local time (resolve it against that zone) or a
+    // floating local time with no zone at all (treat it as the system
+    // zone), but either way we must emit a real UTC instant -
+    // `DateTime::parse_from_rfc3339` rejects an offset-less string, and a
+    // bare "no offset, no Z" value is not valid RFC3339.
+    let date_time = if is_utc {
+        format!(
+            "{}-{}-{}T{}:{}:{}Z",
+            &naive[0..4],
+            &naive[4..6],
+            &naive[6..8],
+            &naive[9..11],
+            &naive[11..13],
+            &naive[13..15],
+        )
+    } else if let Some(naive_dt) = naive_dt {
+        let utc = match tzid.as_deref().and_then(|tz| tz.parse::<chrono_tz::Tz>().ok()) {
+            Some(tz) => tz
+                .from_local_datetime(&naive_dt)
+                .single()
+                .unwrap_or_else(|| tz.from_utc_datetime(&naive_dt))
+                .with_timezone(&chrono::Utc),
+            None => chrono::Local
+                .from_local_datetime(&naive_dt)
+                .single()
+                .unwrap_or_else(|| chrono::Local.from_utc_datetime(&naive_dt))
+                .with_timezone(&chrono::Utc),
+        };
+        utc.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+    } else {
+        value.to_string()
+    };
+
+    EventDateTime {
+        date_time: Some(date_time),
+        date: None,
+        time_zone: tzid,
+    }
+}
+
+/// Unfold iCalendar continuation lines (a line starting with a space or
+/// tab is a continuation of the previous line) and split on CRLF/LF.
+fn unfold_lines(ics: &str) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    for raw in ics.split(['\r', '\n']) {
+        if raw.is_empty() {
+            continue;
+        }
+        if (raw.starts_with(' ') || raw.starts_with('\t')) && !out.is_empty() {
+            let last = out.last_mut().unwrap();
+            last.push_str(raw.trim_start_matches([' ', '\t']));
+        } else {
+            out.push(raw.to_string());
+        }
+    }
+    out
+}
@@ -1,6 +1,10 @@
 //! FGP Calendar Daemon
 //!
-//! Fast daemon for Google Calendar operations. Uses a Python CLI helper for Calendar API calls.
+//! Fast daemon for calendar operations, backed by one of three providers:
+//! the native Google Calendar v3 client (default), a CalDAV client for
+//! self-hosted calendars, or the legacy Python CLI helper kept as a
+//! fallback. Select with `FGP_CALENDAR_BACKEND` (`google`, `caldav`, or
+//! `python`).
 //!
 //! # Methods
 //! - `today` - Get today's events
@@ -10,9 +14,9 @@
 //! - `free_slots` - Find available time slots
 //!
 //! # Setup
-//! 1. Place Google OAuth credentials in ~/.fgp/auth/google/credentials.json
-//! 2. Run once to complete OAuth flow
-//! 3. Daemon will use cached tokens for subsequent calls
+//! - Google: place OAuth credentials in ~/.fgp/auth/google/credentials.json
+//! - CalDAV: place `{caldav_base_url, username, app_password}` in
+//!   ~/.fgp/auth/caldav/credentials.json
 //!
 //! # Run
 //! ```bash
@@ -26,82 +30,74 @@
 //! fgp call calendar.free_slots -p '{"duration_minutes": 30}'
 //! ```
 
+mod backend;
+mod caldav;
+mod google;
+mod ical;
+mod model;
+mod python_cli;
+mod rrule;
+mod scheduling;
+
 use anyhow::{bail, Context, Result};
+use backend::CalendarBackend;
+use chrono::{Duration, Local, SecondsFormat, TimeZone};
 use fgp_daemon::service::{HealthStatus, MethodInfo, ParamInfo};
 use fgp_daemon::{FgpServer, FgpService};
+use model::Event;
+use python_cli::PythonCli;
 use serde_json::Value;
 use std::collections::HashMap;
-use std::path::PathBuf;
-use std::process::Command;
-
-/// Path to the Calendar CLI helper script.
-fn calendar_cli_path() -> PathBuf {
-    // First check next to the binary
-    let exe_dir = std::env::current_exe()
-        .ok()
-        .and_then(|p| p.parent().map(|d| d.to_path_buf()));
-
-    if let Some(dir) = exe_dir {
-        let script = dir.join("calendar-cli.py");
-        if script.exists() {
-            return script;
-        }
-        // Check in scripts/ relative to binary
-        let script = dir.join("scripts").join("calendar-cli.py");
-        if script.exists() {
-            return script;
+
+/// Which backend actually serves Calendar API calls.
+enum Backend {
+    /// The native Google or CalDAV client, behind the shared trait.
+    Async(Box<dyn CalendarBackend>),
+    /// Legacy Python CLI fallback, predates the trait and is synchronous.
+    Python(PythonCli),
+}
+
+impl Backend {
+    /// Selected via `FGP_CALENDAR_BACKEND` (`google`, the default,
+    /// `caldav`, or `python`).
+    fn select() -> Result<Self> {
+        match std::env::var("FGP_CALENDAR_BACKEND").as_deref() {
+            Ok("python") => Ok(Backend::Python(PythonCli::new()?)),
+            Ok("caldav") => Ok(Backend::Async(Box::new(caldav::CalDavClient::new()?))),
+            Ok("google") | Err(_) => Ok(Backend::Async(Box::new(google::CalendarClient::new()?))),
+            Ok(other) => {
+                bail!("Unknown FGP_CALENDAR_BACKEND: {other} (expected google, caldav, or python)")
+            }
         }
     }
 
-    // Check ~/.fgp/services/calendar/calendar-cli.py
-    if let Some(home) = dirs::home_dir() {
-        let script = home.join(".fgp/services/calendar/calendar-cli.py");
-        if script.exists() {
-            return script;
+    fn name(&self) -> &str {
+        match self {
+            Backend::Async(b) => b.name(),
+            Backend::Python(_) => "python",
         }
     }
-
-    // Fallback - assume it's in the cargo project
-    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("scripts/calendar-cli.py")
 }
 
-/// Calendar service using Python CLI for API calls.
+/// Calendar service, backed by whichever provider [`Backend::select`]
+/// picked.
 struct CalendarService {
-    cli_path: PathBuf,
+    backend: Backend,
+    /// Single-threaded runtime used to drive the async backends' calls
+    /// from this service's synchronous `dispatch`.
+    rt: tokio::runtime::Runtime,
 }
 
 impl CalendarService {
     fn new() -> Result<Self> {
-        let cli_path = calendar_cli_path();
-        if !cli_path.exists() {
-            bail!(
-                "Calendar CLI not found at: {}\nEnsure calendar-cli.py is installed.",
-                cli_path.display()
-            );
-        }
-        Ok(Self { cli_path })
-    }
-
-    /// Run the Calendar CLI helper and parse JSON output.
-    fn run_cli(&self, args: &[&str]) -> Result<Value> {
-        let output = Command::new("python3")
-            .arg(&self.cli_path)
-            .args(args)
-            .output()
-            .context("Failed to run calendar-cli.py")?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            // Try to parse JSON error from stdout
-            if let Ok(error_json) = serde_json::from_slice::<Value>(&output.stdout) {
-                if let Some(error) = error_json.get("error").and_then(|e| e.as_str()) {
-                    bail!("Calendar API error: {}", error);
-                }
-            }
-            bail!("calendar-cli failed: {}", stderr);
-        }
-
-        serde_json::from_slice(&output.stdout).context("Failed to parse calendar-cli output")
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .context("building Tokio runtime")?;
+        Ok(Self {
+            backend: Backend::select()?,
+            rt,
+        })
     }
 }
 
@@ -121,6 +117,11 @@ impl FgpService for CalendarService {
             "calendar.search" => self.search(params),
             "calendar.create" => self.create(params),
             "calendar.free_slots" => self.free_slots(params),
+            "calendar.export" => self.export(params),
+            "calendar.import" => self.import(params),
+            "calendar.update" => self.update(params),
+            "calendar.delete" => self.delete(params),
+            "calendar.purge" => self.purge(params),
             _ => bail!("Unknown method: {}", method),
         }
     }
@@ -196,11 +197,35 @@ impl FgpService for CalendarService {
                         required: false,
                         default: None,
                     },
+                    ParamInfo {
+                        name: "timezone".into(),
+                        param_type: "string".into(),
+                        required: false,
+                        default: None,
+                    },
+                    ParamInfo {
+                        name: "rrule".into(),
+                        param_type: "string".into(),
+                        required: false,
+                        default: None,
+                    },
+                    ParamInfo {
+                        name: "location".into(),
+                        param_type: "string".into(),
+                        required: false,
+                        default: None,
+                    },
+                    ParamInfo {
+                        name: "attendees".into(),
+                        param_type: "array".into(),
+                        required: false,
+                        default: None,
+                    },
                 ],
             },
             MethodInfo {
                 name: "calendar.free_slots".into(),
-                description: "Find available time slots".into(),
+                description: "Find available time slots within working hours".into(),
                 params: vec![
                     ParamInfo {
                         name: "duration_minutes".into(),
@@ -214,51 +239,220 @@ impl FgpService for CalendarService {
                         required: false,
                         default: Some(Value::Number(7.into())),
                     },
+                    ParamInfo {
+                        name: "work_start".into(),
+                        param_type: "string".into(),
+                        required: false,
+                        default: Some(Value::String("09:00".into())),
+                    },
+                    ParamInfo {
+                        name: "work_end".into(),
+                        param_type: "string".into(),
+                        required: false,
+                        default: Some(Value::String("17:00".into())),
+                    },
+                    ParamInfo {
+                        name: "work_days".into(),
+                        param_type: "array".into(),
+                        required: false,
+                        default: None,
+                    },
+                    ParamInfo {
+                        name: "lunch_start".into(),
+                        param_type: "string".into(),
+                        required: false,
+                        default: None,
+                    },
+                    ParamInfo {
+                        name: "lunch_end".into(),
+                        param_type: "string".into(),
+                        required: false,
+                        default: None,
+                    },
+                    ParamInfo {
+                        name: "granularity_minutes".into(),
+                        param_type: "integer".into(),
+                        required: false,
+                        default: Some(Value::Number(15.into())),
+                    },
+                    ParamInfo {
+                        name: "max_results".into(),
+                        param_type: "integer".into(),
+                        required: false,
+                        default: Some(Value::Number(20.into())),
+                    },
+                ],
+            },
+            MethodInfo {
+                name: "calendar.export".into(),
+                description: "Export a time range of events as a VCALENDAR string".into(),
+                params: vec![
+                    ParamInfo {
+                        name: "start".into(),
+                        param_type: "string".into(),
+                        required: true,
+                        default: None,
+                    },
+                    ParamInfo {
+                        name: "end".into(),
+                        param_type: "string".into(),
+                        required: true,
+                        default: None,
+                    },
+                ],
+            },
+            MethodInfo {
+                name: "calendar.import".into(),
+                description:
+                    "Import events from an .ics file or inline string, skipping existing UIDs"
+                        .into(),
+                params: vec![
+                    ParamInfo {
+                        name: "path".into(),
+                        param_type: "string".into(),
+                        required: false,
+                        default: None,
+                    },
+                    ParamInfo {
+                        name: "ics".into(),
+                        param_type: "string".into(),
+                        required: false,
+                        default: None,
+                    },
+                ],
+            },
+            MethodInfo {
+                name: "calendar.update".into(),
+                description: "Update fields on an existing event".into(),
+                params: vec![
+                    ParamInfo {
+                        name: "id".into(),
+                        param_type: "string".into(),
+                        required: true,
+                        default: None,
+                    },
+                    ParamInfo {
+                        name: "summary".into(),
+                        param_type: "string".into(),
+                        required: false,
+                        default: None,
+                    },
+                    ParamInfo {
+                        name: "start".into(),
+                        param_type: "string".into(),
+                        required: false,
+                        default: None,
+                    },
+                    ParamInfo {
+                        name: "end".into(),
+                        param_type: "string".into(),
+                        required: false,
+                        default: None,
+                    },
+                    ParamInfo {
+                        name: "description".into(),
+                        param_type: "string".into(),
+                        required: false,
+                        default: None,
+                    },
+                    ParamInfo {
+                        name: "rrule".into(),
+                        param_type: "string".into(),
+                        required: false,
+                        default: None,
+                    },
+                ],
+            },
+            MethodInfo {
+                name: "calendar.delete".into(),
+                description: "Delete an event by id".into(),
+                params: vec![ParamInfo {
+                    name: "id".into(),
+                    param_type: "string".into(),
+                    required: true,
+                    default: None,
+                }],
+            },
+            MethodInfo {
+                name: "calendar.purge".into(),
+                description:
+                    "Delete every event matching a query or date range; requires confirm=true"
+                        .into(),
+                params: vec![
+                    ParamInfo {
+                        name: "confirm".into(),
+                        param_type: "boolean".into(),
+                        required: true,
+                        default: None,
+                    },
+                    ParamInfo {
+                        name: "query".into(),
+                        param_type: "string".into(),
+                        required: false,
+                        default: None,
+                    },
+                    ParamInfo {
+                        name: "start".into(),
+                        param_type: "string".into(),
+                        required: false,
+                        default: None,
+                    },
+                    ParamInfo {
+                        name: "end".into(),
+                        param_type: "string".into(),
+                        required: false,
+                        default: None,
+                    },
                 ],
             },
         ]
     }
 
     fn on_start(&self) -> Result<()> {
-        // Verify Calendar CLI exists and Python is available
-        let output = Command::new("python3")
-            .arg("--version")
-            .output()
-            .context("Python3 not found")?;
-
-        if !output.status.success() {
-            bail!("Python3 not available");
+        match &self.backend {
+            Backend::Async(b) => {
+                tracing::info!(backend = b.name(), "Calendar daemon starting");
+            }
+            Backend::Python(cli) => {
+                cli.check_available()?;
+                tracing::info!(
+                    cli_path = %cli.cli_path().display(),
+                    "Calendar daemon starting (python backend)"
+                );
+            }
         }
-
-        tracing::info!(
-            cli_path = %self.cli_path.display(),
-            "Calendar daemon starting"
-        );
         Ok(())
     }
 
     fn health_check(&self) -> HashMap<String, HealthStatus> {
         let mut status = HashMap::new();
 
-        // Check if CLI exists
-        if self.cli_path.exists() {
-            status.insert(
-                "calendar_cli".into(),
-                HealthStatus {
-                    ok: true,
-                    latency_ms: None,
-                    message: Some(format!("CLI at {}", self.cli_path.display())),
-                },
-            );
-        } else {
-            status.insert(
-                "calendar_cli".into(),
-                HealthStatus {
-                    ok: false,
-                    latency_ms: None,
-                    message: Some("calendar-cli.py not found".into()),
-                },
-            );
+        status.insert(
+            "backend".into(),
+            HealthStatus {
+                ok: true,
+                latency_ms: None,
+                message: Some(self.backend.name().to_string()),
+            },
+        );
+
+        match &self.backend {
+            Backend::Async(b) => status.extend(b.health()),
+            Backend::Python(cli) => {
+                let ok = cli.cli_path().exists();
+                status.insert(
+                    "calendar_cli".into(),
+                    HealthStatus {
+                        ok,
+                        latency_ms: None,
+                        message: Some(if ok {
+                            format!("CLI at {}", cli.cli_path().display())
+                        } else {
+                            "calendar-cli.py not found".into()
+                        }),
+                    },
+                );
+            }
         }
 
         status
@@ -268,22 +462,54 @@ impl FgpService for CalendarService {
 impl CalendarService {
     /// Get today's events.
     fn today(&self) -> Result<Value> {
-        self.run_cli(&["today"])
+        match &self.backend {
+            Backend::Async(client) => {
+                let now = Local::now();
+                let start = now.date_naive().and_hms_opt(0, 0, 0).unwrap();
+                let start = Local.from_local_datetime(&start).unwrap();
+                let end = start + Duration::days(1);
+                let events = self.rt.block_on(client.list_events(
+                    &rfc3339(query_time_min(start)),
+                    &rfc3339(end),
+                    None,
+                    2500,
+                ))?;
+                let events = rrule::expand(events, start, end)?;
+                events_to_value(events)
+            }
+            Backend::Python(cli) => cli.run_cli(&["today"]),
+        }
     }
 
     /// Get upcoming events.
     fn upcoming(&self, params: HashMap<String, Value>) -> Result<Value> {
-        let days = params
-            .get("days")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(7);
-
-        let limit = params
-            .get("limit")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(20);
-
-        self.run_cli(&["upcoming", "--days", &days.to_string(), "--limit", &limit.to_string()])
+        let days = params.get("days").and_then(|v| v.as_u64()).unwrap_or(7);
+        let limit = params.get("limit").and_then(|v| v.as_u64()).unwrap_or(20);
+
+        match &self.backend {
+            Backend::Async(client) => {
+                let now = Local::now();
+                let end = now + Duration::days(days as i64);
+                // Fetch masters too (singleEvents=false), then expand past
+                // `limit` so the instances themselves can be limited below.
+                let events = self.rt.block_on(client.list_events(
+                    &rfc3339(query_time_min(now)),
+                    &rfc3339(end),
+                    None,
+                    2500,
+                ))?;
+                let mut events = rrule::expand(events, now, end)?;
+                events.truncate(limit as usize);
+                events_to_value(events)
+            }
+            Backend::Python(cli) => cli.run_cli(&[
+                "upcoming",
+                "--days",
+                &days.to_string(),
+                "--limit",
+                &limit.to_string(),
+            ]),
+        }
     }
 
     /// Search events.
@@ -292,13 +518,23 @@ impl CalendarService {
             .get("query")
             .and_then(|v| v.as_str())
             .context("query parameter is required")?;
-
-        let days = params
-            .get("days")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(30);
-
-        self.run_cli(&["search", query, "--days", &days.to_string()])
+        let days = params.get("days").and_then(|v| v.as_u64()).unwrap_or(30);
+
+        match &self.backend {
+            Backend::Async(client) => {
+                let now = Local::now();
+                let end = now + Duration::days(days as i64);
+                let events = self.rt.block_on(client.list_events(
+                    &rfc3339(query_time_min(now)),
+                    &rfc3339(end),
+                    Some(query),
+                    2500,
+                ))?;
+                let events = rrule::expand(events, now, end)?;
+                events_to_value(events)
+            }
+            Backend::Python(cli) => cli.run_cli(&["search", query, "--days", &days.to_string()]),
+        }
     }
 
     /// Create a new event.
@@ -307,45 +543,372 @@ impl CalendarService {
             .get("summary")
             .and_then(|v| v.as_str())
             .context("summary parameter is required")?;
-
         let start = params
             .get("start")
             .and_then(|v| v.as_str())
             .context("start parameter is required")?;
-
         let end = params
             .get("end")
             .and_then(|v| v.as_str())
             .context("end parameter is required")?;
-
-        let mut args = vec!["create", summary, start, end];
-
-        let description;
-        if let Some(desc) = params.get("description").and_then(|v| v.as_str()) {
-            description = desc.to_string();
-            args.push("--description");
-            args.push(&description);
+        let description = params
+            .get("description")
+            .and_then(|v| v.as_str())
+            .map(str::to_string);
+
+        match &self.backend {
+            Backend::Async(client) => {
+                let timezone = match params.get("timezone").and_then(|v| v.as_str()) {
+                    Some(tz) => {
+                        ical::validate_timezone(tz)?;
+                        tz.to_string()
+                    }
+                    None => ical::system_timezone(),
+                };
+                let start = ical::parse_boundary(start, Some(&timezone))?;
+                let end = ical::parse_boundary(end, Some(&timezone))?;
+
+                let rrule = params
+                    .get("rrule")
+                    .and_then(|v| v.as_str())
+                    .map(|r| vec![format!("RRULE:{r}")]);
+
+                let location = params
+                    .get("location")
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string);
+
+                let attendees = params
+                    .get("attendees")
+                    .and_then(|v| v.as_array())
+                    .map(|list| {
+                        list.iter()
+                            .filter_map(|v| v.as_str())
+                            .map(|email| model::Attendee {
+                                email: email.to_string(),
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                    .filter(|list| !list.is_empty());
+
+                let uid = ical::new_uid();
+                // Google's client-supplied `id` must be 5-1024 lowercase
+                // base32hex characters; our own UID form (with `@` and
+                // hyphens) only works as-is for CalDAV, which addresses
+                // events by filename rather than validating the id.
+                let event_id = if client.name() == "google" {
+                    ical::google_safe_id(&uid)
+                } else {
+                    uid
+                };
+
+                let event = Event {
+                    id: Some(event_id),
+                    summary: summary.to_string(),
+                    description,
+                    start,
+                    end,
+                    location,
+                    attendees,
+                    recurrence: rrule,
+                    recurrence_id: None,
+                    html_link: None,
+                };
+                let created = self.rt.block_on(client.insert_event(&event))?;
+                Ok(serde_json::to_value(created)?)
+            }
+            Backend::Python(cli) => {
+                let mut args = vec!["create", summary, start, end];
+                if let Some(desc) = &description {
+                    args.push("--description");
+                    args.push(desc);
+                }
+                cli.run_cli(&args)
+            }
         }
-
-        self.run_cli(&args)
     }
 
-    /// Find free time slots.
+    /// Find free time slots, intersecting the calendar's busy intervals
+    /// against configurable working hours.
     fn free_slots(&self, params: HashMap<String, Value>) -> Result<Value> {
         let duration = params
             .get("duration_minutes")
-            .and_then(|v| v.as_u64())
+            .and_then(|v| v.as_i64())
             .context("duration_minutes parameter is required")?;
-
-        let days = params
-            .get("days")
+        let days = params.get("days").and_then(|v| v.as_u64()).unwrap_or(7);
+        let granularity = params
+            .get("granularity_minutes")
+            .and_then(|v| v.as_i64())
+            .unwrap_or(15);
+        let max_results = params
+            .get("max_results")
             .and_then(|v| v.as_u64())
-            .unwrap_or(7);
+            .unwrap_or(20) as usize;
+        let work_days: Option<Vec<String>> = params.get("work_days").and_then(|v| v.as_array()).map(|a| {
+            a.iter()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect()
+        });
+        let hours = scheduling::WorkingHours::parse(
+            params.get("work_start").and_then(|v| v.as_str()),
+            params.get("work_end").and_then(|v| v.as_str()),
+            work_days.as_deref(),
+            params.get("lunch_start").and_then(|v| v.as_str()),
+            params.get("lunch_end").and_then(|v| v.as_str()),
+        )?;
+
+        match &self.backend {
+            Backend::Async(client) => {
+                let now = Local::now();
+                let end = now + Duration::days(days as i64);
+                let events = self.rt.block_on(client.list_events(
+                    &rfc3339(query_time_min(now)),
+                    &rfc3339(end),
+                    None,
+                    2500,
+                ))?;
+                let events = rrule::expand(events, now, end)?;
+                let slots = scheduling::find_slots(
+                    &events,
+                    now,
+                    end,
+                    duration,
+                    &hours,
+                    granularity,
+                    max_results,
+                );
+                Ok(serde_json::json!({
+                    "duration_minutes": duration,
+                    "days": days,
+                    "slots": slots,
+                }))
+            }
+            Backend::Python(cli) => cli.run_cli(&[
+                "free-slots",
+                "--duration",
+                &duration.to_string(),
+                "--days",
+                &days.to_string(),
+            ]),
+        }
+    }
+
+    /// Export a time range of events as a single VCALENDAR string.
+    fn export(&self, params: HashMap<String, Value>) -> Result<Value> {
+        let start = params
+            .get("start")
+            .and_then(|v| v.as_str())
+            .context("start parameter is required")?;
+        let end = params
+            .get("end")
+            .and_then(|v| v.as_str())
+            .context("end parameter is required")?;
+
+        match &self.backend {
+            Backend::Async(client) => {
+                // Recurring masters are exported as-is (RRULE intact), not
+                // expanded into instances, so the import side round-trips
+                // the recurrence rather than duplicating every occurrence.
+                let events = self.rt.block_on(client.list_events(start, end, None, 2500))?;
+                Ok(serde_json::json!({ "ics": ical::build_vcalendar(&events) }))
+            }
+            Backend::Python(_) => bail!("calendar.export is not supported by the python backend"),
+        }
+    }
+
+    /// Import events from an `.ics` file or inline string, skipping any
+    /// whose UID already exists so re-imports are idempotent.
+    fn import(&self, params: HashMap<String, Value>) -> Result<Value> {
+        let path = params.get("path").and_then(|v| v.as_str());
+        let inline = params.get("ics").and_then(|v| v.as_str());
+        let ics = match (path, inline) {
+            (Some(path), _) => std::fs::read_to_string(path)
+                .with_context(|| format!("reading ICS file at {path}"))?,
+            (None, Some(ics)) => ics.to_string(),
+            (None, None) => bail!("either 'path' or 'ics' parameter is required"),
+        };
+
+        let incoming = ical::parse_vevents(&ics);
+        if incoming.is_empty() {
+            return Ok(serde_json::json!({ "imported": 0, "skipped": 0 }));
+        }
+
+        match &self.backend {
+            Backend::Async(client) => {
+                let starts: Vec<_> = incoming
+                    .iter()
+                    .filter_map(|e| e.start.to_local().ok())
+                    .collect();
+                let ends: Vec<_> = incoming
+                    .iter()
+                    .filter_map(|e| e.end.to_local().ok())
+                    .collect();
+                let window_start = starts.iter().min().copied().unwrap_or_else(Local::now);
+                let window_end = ends
+                    .iter()
+                    .max()
+                    .copied()
+                    .unwrap_or_else(|| window_start + Duration::days(1));
+
+                let existing = self.rt.block_on(client.list_events(
+                    &rfc3339(window_start),
+                    &rfc3339(window_end),
+                    None,
+                    2500,
+                ))?;
+                let existing_uids: std::collections::HashSet<_> =
+                    existing.into_iter().filter_map(|e| e.id).collect();
+
+                let is_google = client.name() == "google";
+
+                let mut imported = 0;
+                let mut skipped = 0;
+                for mut event in incoming {
+                    // Mirror the mapping `create` applies: Google only
+                    // accepts a lowercase base32hex id, so the ICS UID has
+                    // to be hashed into one. Deriving it deterministically
+                    // from the UID (rather than letting Google assign one)
+                    // keeps re-imports idempotent.
+                    if is_google {
+                        event.id = event.id.as_deref().map(ical::google_safe_id);
+                    }
+                    if event
+                        .id
+                        .as_ref()
+                        .is_some_and(|id| existing_uids.contains(id))
+                    {
+                        skipped += 1;
+                        continue;
+                    }
+                    self.rt.block_on(client.insert_event(&event))?;
+                    imported += 1;
+                }
+
+                Ok(serde_json::json!({ "imported": imported, "skipped": skipped }))
+            }
+            Backend::Python(_) => bail!("calendar.import is not supported by the python backend"),
+        }
+    }
+
+    /// Update fields on an existing event, leaving unspecified fields as-is.
+    fn update(&self, params: HashMap<String, Value>) -> Result<Value> {
+        let id = params
+            .get("id")
+            .and_then(|v| v.as_str())
+            .context("id parameter is required")?;
 
-        self.run_cli(&["free-slots", "--duration", &duration.to_string(), "--days", &days.to_string()])
+        match &self.backend {
+            Backend::Async(client) => {
+                let mut event = self.rt.block_on(client.get_event(id))?;
+
+                if let Some(summary) = params.get("summary").and_then(|v| v.as_str()) {
+                    event.summary = summary.to_string();
+                }
+                if let Some(description) = params.get("description").and_then(|v| v.as_str()) {
+                    event.description = Some(description.to_string());
+                }
+                if let Some(start) = params.get("start").and_then(|v| v.as_str()) {
+                    event.start = ical::parse_boundary(start, event.start.time_zone.clone().as_deref())?;
+                }
+                if let Some(end) = params.get("end").and_then(|v| v.as_str()) {
+                    event.end = ical::parse_boundary(end, event.end.time_zone.clone().as_deref())?;
+                }
+                if let Some(rrule) = params.get("rrule").and_then(|v| v.as_str()) {
+                    event.recurrence = Some(vec![format!("RRULE:{rrule}")]);
+                }
+
+                let updated = self.rt.block_on(client.update_event(id, &event))?;
+                Ok(serde_json::to_value(updated)?)
+            }
+            Backend::Python(_) => bail!("calendar.update is not supported by the python backend"),
+        }
+    }
+
+    /// Delete a single event by id.
+    fn delete(&self, params: HashMap<String, Value>) -> Result<Value> {
+        let id = params
+            .get("id")
+            .and_then(|v| v.as_str())
+            .context("id parameter is required")?;
+
+        match &self.backend {
+            Backend::Async(client) => {
+                self.rt.block_on(client.delete_event(id))?;
+                Ok(serde_json::json!({ "deleted": id }))
+            }
+            Backend::Python(_) => bail!("calendar.delete is not supported by the python backend"),
+        }
+    }
+
+    /// Delete every event matching a query and/or date range. Guarded
+    /// behind an explicit `confirm: true` so a malformed call can't wipe a
+    /// calendar by accident.
+    fn purge(&self, params: HashMap<String, Value>) -> Result<Value> {
+        if !params.get("confirm").and_then(|v| v.as_bool()).unwrap_or(false) {
+            bail!("calendar.purge requires an explicit {{\"confirm\": true}} parameter");
+        }
+        let query = params.get("query").and_then(|v| v.as_str());
+        // `confirm` alone isn't a scope - without a query or an explicit
+        // range, the call would default to wiping the next 365 days of
+        // the calendar. Require the caller to say what they mean to
+        // delete.
+        if query.is_none()
+            && params.get("start").is_none()
+            && params.get("end").is_none()
+        {
+            bail!(
+                "calendar.purge requires at least one of 'query', 'start', or 'end' \
+                 in addition to confirm, to avoid wiping the entire default window"
+            );
+        }
+
+        match &self.backend {
+            Backend::Async(client) => {
+                let start = match params.get("start").and_then(|v| v.as_str()) {
+                    Some(s) => ical::parse_boundary(s, None)?.to_local()?,
+                    None => Local::now(),
+                };
+                let end = match params.get("end").and_then(|v| v.as_str()) {
+                    Some(s) => ical::parse_boundary(s, None)?.to_local()?,
+                    None => start + Duration::days(365),
+                };
+
+                let events =
+                    self.rt
+                        .block_on(client.list_events(&rfc3339(start), &rfc3339(end), query, 2500))?;
+
+                let mut deleted = 0;
+                for event in events {
+                    let Some(id) = event.id else { continue };
+                    self.rt.block_on(client.delete_event(&id))?;
+                    deleted += 1;
+                }
+
+                Ok(serde_json::json!({ "deleted": deleted }))
+            }
+            Backend::Python(_) => bail!("calendar.purge is not supported by the python backend"),
+        }
     }
 }
 
+fn events_to_value(events: Vec<Event>) -> Result<Value> {
+    Ok(serde_json::json!({ "events": events }))
+}
+
+fn rfc3339(dt: chrono::DateTime<Local>) -> String {
+    dt.to_rfc3339_opts(SecondsFormat::Secs, false)
+}
+
+/// Widen a query window's lower bound so a recurring series that began
+/// before `start` still comes back from `events.list`: with
+/// `singleEvents=false` the API filters masters by their own DTSTART, not
+/// by whether any instance overlaps the window, so without this a
+/// standing weekly meeting that started months ago would never be
+/// fetched (and therefore never expanded by `rrule::expand`).
+fn query_time_min(start: chrono::DateTime<Local>) -> chrono::DateTime<Local> {
+    start - Duration::days(rrule::LOOKBACK_DAYS)
+}
+
 fn main() -> Result<()> {
     // Initialize tracing
     tracing_subscriber::fmt()
@@ -0,0 +1,89 @@
+//! Calendar event model shared by every backend.
+//!
+//! Both the Google ([`crate::google`]) and CalDAV ([`crate::caldav`])
+//! backends read and write this same shape — Google maps it to/from its
+//! REST JSON, CalDAV maps it to/from iCalendar VEVENTs via [`crate::ical`]
+//! — so the rest of the daemon (RRULE expansion, `dispatch`) never needs
+//! to know which backend is active.
+
+use anyhow::Context;
+use chrono::TimeZone;
+use serde::{Deserialize, Serialize};
+
+/// A single calendar event, independent of backend representation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Event {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub summary: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub start: EventDateTime,
+    pub end: EventDateTime,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attendees: Option<Vec<Attendee>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recurrence: Option<Vec<String>>,
+    /// Set on occurrences synthesized by [`crate::rrule::expand`] from a
+    /// recurring master event; mirrors the field Google itself returns
+    /// when expanding recurrence server-side.
+    #[serde(rename = "recurringEventId", skip_serializing_if = "Option::is_none")]
+    pub recurrence_id: Option<String>,
+    #[serde(rename = "htmlLink", skip_serializing_if = "Option::is_none")]
+    pub html_link: Option<String>,
+}
+
+/// Either a timed (`date_time`) or all-day (`date`) event boundary.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventDateTime {
+    #[serde(rename = "dateTime", skip_serializing_if = "Option::is_none")]
+    pub date_time: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    #[serde(rename = "timeZone", skip_serializing_if = "Option::is_none")]
+    pub time_zone: Option<String>,
+}
+
+impl EventDateTime {
+    /// Parse this boundary into a concrete local date-time, treating an
+    /// all-day `date` as local midnight.
+    pub fn to_local(&self) -> anyhow::Result<chrono::DateTime<chrono::Local>> {
+        if let Some(date_time) = &self.date_time {
+            return chrono::DateTime::parse_from_rfc3339(date_time)
+                .map(|d| d.with_timezone(&chrono::Local))
+                .with_context(|| format!("invalid event dateTime: {date_time}"));
+        }
+        if let Some(date) = &self.date {
+            let naive = chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .with_context(|| format!("invalid event date: {date}"))?
+                .and_hms_opt(0, 0, 0)
+                .unwrap();
+            return Ok(resolve_local(naive));
+        }
+        anyhow::bail!("event boundary has neither dateTime nor date")
+    }
+}
+
+/// Resolve a wall-clock `NaiveDateTime` to a concrete local instant,
+/// without panicking on a DST fall-back/spring-forward edge case: an
+/// ambiguous time (fall back) resolves to the earlier instant, and a
+/// nonexistent time (spring forward) falls back to treating the
+/// wall-clock value as if it were already UTC. Same pattern as
+/// `rrule::naive_to_local`.
+fn resolve_local(naive: chrono::NaiveDateTime) -> chrono::DateTime<chrono::Local> {
+    match chrono::Local.from_local_datetime(&naive) {
+        chrono::LocalResult::Single(dt) => dt,
+        chrono::LocalResult::Ambiguous(earliest, _latest) => earliest,
+        chrono::LocalResult::None => chrono::Local.from_utc_datetime(&naive),
+    }
+}
+
+/// An event attendee, identified by email (matches the shape of Google's
+/// `Events.attendees[]` and becomes an `ATTENDEE:mailto:...` VEVENT line
+/// for CalDAV).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Attendee {
+    pub email: String,
+}
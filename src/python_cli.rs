@@ -0,0 +1,95 @@
+//! Legacy Python CLI backend.
+//!
+//! Kept as a fallback behind `FGP_CALENDAR_BACKEND=python` while the native
+//! Rust backend (see [`crate::google`]) bakes in production. Existing
+//! deployments that haven't set up Google OAuth credentials for the native
+//! path keep working unmodified.
+
+use anyhow::{bail, Context, Result};
+use serde_json::Value;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Path to the Calendar CLI helper script.
+fn calendar_cli_path() -> PathBuf {
+    let exe_dir = std::env::current_exe()
+        .ok()
+        .and_then(|p| p.parent().map(|d| d.to_path_buf()));
+
+    if let Some(dir) = exe_dir {
+        let script = dir.join("calendar-cli.py");
+        if script.exists() {
+            return script;
+        }
+        let script = dir.join("scripts").join("calendar-cli.py");
+        if script.exists() {
+            return script;
+        }
+    }
+
+    if let Some(home) = dirs::home_dir() {
+        let script = home.join(".fgp/services/calendar/calendar-cli.py");
+        if script.exists() {
+            return script;
+        }
+    }
+
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("scripts/calendar-cli.py")
+}
+
+/// Calendar backend that shells out to the Python CLI for every request.
+pub struct PythonCli {
+    cli_path: PathBuf,
+}
+
+impl PythonCli {
+    pub fn new() -> Result<Self> {
+        let cli_path = calendar_cli_path();
+        if !cli_path.exists() {
+            bail!(
+                "Calendar CLI not found at: {}\nEnsure calendar-cli.py is installed.",
+                cli_path.display()
+            );
+        }
+        Ok(Self { cli_path })
+    }
+
+    pub fn cli_path(&self) -> &std::path::Path {
+        &self.cli_path
+    }
+
+    /// Run the Calendar CLI helper and parse JSON output.
+    pub fn run_cli(&self, args: &[&str]) -> Result<Value> {
+        let output = Command::new("python3")
+            .arg(&self.cli_path)
+            .args(args)
+            .output()
+            .context("Failed to run calendar-cli.py")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if let Ok(error_json) = serde_json::from_slice::<Value>(&output.stdout) {
+                if let Some(error) = error_json.get("error").and_then(|e| e.as_str()) {
+                    bail!("Calendar API error: {}", error);
+                }
+            }
+            bail!("calendar-cli failed: {}", stderr);
+        }
+
+        serde_json::from_slice(&output.stdout).context("Failed to parse calendar-cli output")
+    }
+
+    /// Verify a Python interpreter is available. The script's presence is
+    /// checked once, in [`PythonCli::new`].
+    pub fn check_available(&self) -> Result<()> {
+        let output = Command::new("python3")
+            .arg("--version")
+            .output()
+            .context("Python3 not found")?;
+
+        if !output.status.success() {
+            bail!("Python3 not available");
+        }
+        Ok(())
+    }
+}
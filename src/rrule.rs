@@ -0,0 +1,459 @@
+//! RRULE expansion.
+//!
+//! Google (and iCalendar generally) represents a recurring event as one
+//! "master" event whose `recurrence` field holds RFC 5545 `RRULE`/`EXDATE`
+//! lines, plus the master's own `DTSTART`/`DTEND` giving the first
+//! occurrence and its duration. `events.list` normally expands these for us
+//! when `singleEvents=true`, but we request `singleEvents=false` (see
+//! [`crate::google::client`]) so we can expand them ourselves and reuse the
+//! same logic against the CalDAV backend, which hands back raw VEVENTs.
+//!
+//! Only the common subset of RRULE used by real calendars is implemented:
+//! `FREQ` of DAILY/WEEKLY/MONTHLY/YEARLY, `INTERVAL`, `COUNT` or `UNTIL`
+//! termination, `BYDAY` weekday filters (weekly only), and `EXDATE` removal.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Datelike, Duration, Local, TimeZone, Weekday};
+
+use crate::model::{Event, EventDateTime};
+
+/// How far back from "now" we'll still expand occurrences, so that an
+/// unbounded (no COUNT/UNTIL) rule doesn't generate occurrences forever.
+///
+/// Callers that fetch events before expanding (see `crate::main`) must
+/// also widen their network query's `time_min` by this much: Google's
+/// `events.list` (with `singleEvents=false`) filters recurring masters by
+/// their *own* DTSTART, so a series that began before the query window
+/// would never come back from the API at all, let alone get expanded.
+pub(crate) const LOOKBACK_DAYS: i64 = 30;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Freq {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+#[derive(Debug, Clone)]
+struct Rule {
+    freq: Freq,
+    interval: u32,
+    count: Option<u32>,
+    until: Option<DateTime<Local>>,
+    by_day: Vec<Weekday>,
+}
+
+impl Rule {
+    fn parse(line: &str) -> Result<Self> {
+        let body = line.strip_prefix("RRULE:").unwrap_or(line);
+
+        let mut freq = None;
+        let mut interval = 1u32;
+        let mut count = None;
+        let mut until = None;
+        let mut by_day = Vec::new();
+
+        for part in body.split(';') {
+            let Some((key, value)) = part.split_once('=') else {
+                continue;
+            };
+            match key {
+                "FREQ" => {
+                    freq = Some(match value {
+                        "DAILY" => Freq::Daily,
+                        "WEEKLY" => Freq::Weekly,
+                        "MONTHLY" => Freq::Monthly,
+                        "YEARLY" => Freq::Yearly,
+                        other => bail!("unsupported RRULE FREQ: {other}"),
+                    });
+                }
+                "INTERVAL" => {
+                    interval = value.parse().context("invalid RRULE INTERVAL")?;
+                }
+                "COUNT" => {
+                    count = Some(value.parse().context("invalid RRULE COUNT")?);
+                }
+                "UNTIL" => {
+                    until = Some(parse_ical_datetime(value)?);
+                }
+                "BYDAY" => {
+                    for day in value.split(',') {
+                        by_day.push(parse_weekday(day)?);
+                    }
+                }
+                _ => {} // BYMONTHDAY, BYSETPOS, WKST, etc. are out of scope.
+            }
+        }
+
+        Ok(Rule {
+            freq: freq.context("RRULE missing FREQ")?,
+            interval: interval.max(1),
+            count,
+            until,
+            by_day,
+        })
+    }
+}
+
+fn parse_weekday(s: &str) -> Result<Weekday> {
+    // Ordinal prefixes like "1MO"/"-1FR" aren't needed for a plain weekly
+    // BYDAY filter; strip any leading sign/digits.
+    let trimmed = s.trim_start_matches(['+', '-']).trim_start_matches(char::is_numeric);
+    Ok(match trimmed {
+        "MO" => Weekday::Mon,
+        "TU" => Weekday::Tue,
+        "WE" => Weekday::Wed,
+        "TH" => Weekday::Thu,
+        "FR" => Weekday::Fri,
+        "SA" => Weekday::Sat,
+        "SU" => Weekday::Sun,
+        other => bail!("invalid BYDAY value: {other}"),
+    })
+}
+
+/// Parse a basic-format iCalendar date-time (`20260815T090000Z` or
+/// `20260815T090000`), as used in `UNTIL`/`EXDATE`.
+///
+/// A `Z`-suffixed value is a real UTC instant; a bare value is a
+/// wall-clock time in the series' own zone (the same form `DTSTART`
+/// takes), so it must go through `from_local_datetime` like every
+/// occurrence candidate in [`occurrences`] - otherwise `EXDATE` only
+/// excludes the occurrence it names when the local zone is UTC.
+fn parse_ical_datetime(s: &str) -> Result<DateTime<Local>> {
+    let (naive_part, is_utc) = match s.strip_suffix('Z') {
+        Some(rest) => (rest, true),
+        None => (s, false),
+    };
+    let naive = chrono::NaiveDateTime::parse_from_str(naive_part, "%Y%m%dT%H%M%S")
+        .or_else(|_| {
+            chrono::NaiveDate::parse_from_str(naive_part, "%Y%m%d")
+                .map(|d| d.and_hms_opt(0, 0, 0).unwrap())
+        })
+        .with_context(|| format!("invalid iCalendar date-time: {s}"))?;
+    Ok(if is_utc {
+        Local.from_utc_datetime(&naive)
+    } else {
+        naive_to_local(naive)
+    })
+}
+
+/// Parse the `EXDATE` lines in a `recurrence` array into a list of excluded
+/// instance starts.
+fn parse_exdates(recurrence: &[String]) -> Result<Vec<DateTime<Local>>> {
+    let mut out = Vec::new();
+    for line in recurrence {
+        let Some(body) = line.strip_prefix("EXDATE").and_then(|s| {
+            // Handle both "EXDATE:..." and "EXDATE;TZID=...:...".
+            s.rsplit_once(':').map(|(_, v)| v)
+        }) else {
+            continue;
+        };
+        for date in body.split(',') {
+            out.push(parse_ical_datetime(date)?);
+        }
+    }
+    Ok(out)
+}
+
+/// Expand any recurring events in `events` into concrete occurrences that
+/// fall inside `[window_start - LOOKBACK_DAYS, window_end]`, merge them
+/// with the plain singleton events, and return everything sorted by start
+/// time.
+///
+/// Non-recurring events pass through untouched. Recurring events are
+/// dropped from the output and replaced by their expanded instances.
+pub fn expand(
+    events: Vec<Event>,
+    window_start: DateTime<Local>,
+    window_end: DateTime<Local>,
+) -> Result<Vec<Event>> {
+    let mut out = Vec::new();
+
+    for event in events {
+        let Some(recurrence) = event.recurrence.clone() else {
+            out.push(event);
+            continue;
+        };
+
+        let Some(rule_line) = recurrence.iter().find(|l| l.starts_with("RRULE")) else {
+            // RDATE-only or otherwise unrecognized recurrence; pass through.
+            out.push(event);
+            continue;
+        };
+        let rule = Rule::parse(rule_line)?;
+        let exdates = parse_exdates(&recurrence)?;
+
+        let dtstart = parse_event_datetime(&event.start)?;
+        let dtend = parse_event_datetime(&event.end)?;
+        let duration = dtend - dtstart;
+
+        // Never emit occurrences further back than the lookback window,
+        // regardless of how far in the past DTSTART is. Stepping itself
+        // still starts at DTSTART so that COUNT is applied correctly.
+        let expand_from = window_start - Duration::days(LOOKBACK_DAYS);
+
+        for start in occurrences(&rule, dtstart, expand_from.max(dtstart), window_end) {
+            if exdates.iter().any(|ex| *ex == start) {
+                continue;
+            }
+            let end = start + duration;
+            let mut instance = event.clone();
+            instance.start = to_event_datetime(start, event.start.time_zone.clone());
+            instance.end = to_event_datetime(end, event.end.time_zone.clone());
+            instance.recurrence = None;
+            instance.recurrence_id = Some(format!(
+                "{}_{}",
+                event.id.clone().unwrap_or_default(),
+                start.to_rfc3339_opts(chrono::SecondsFormat::Secs, false)
+            ));
+            out.push(instance);
+        }
+    }
+
+    out.sort_by_key(|e| parse_event_datetime(&e.start).ok());
+    Ok(out)
+}
+
+/// Generate each occurrence start of `rule` (beginning at `dtstart`) that
+/// falls within `[from, to]`, applying `COUNT`/`UNTIL` termination.
+///
+/// Stepping is done on the *wall-clock* `NaiveDateTime` (calendar
+/// arithmetic), converting to a concrete `DateTime<Local>` only to
+/// compare/emit each candidate. Stepping on `DateTime<Local>` directly via
+/// `Duration` addition is absolute-time arithmetic, which would silently
+/// shift a daily/weekly recurrence by an hour across a DST transition.
+fn occurrences(
+    rule: &Rule,
+    dtstart: DateTime<Local>,
+    from: DateTime<Local>,
+    to: DateTime<Local>,
+) -> Vec<DateTime<Local>> {
+    let mut out = Vec::new();
+    let mut current_naive = dtstart.naive_local();
+    let mut emitted = 0u32;
+
+    loop {
+        let current = naive_to_local(current_naive);
+
+        if let Some(until) = rule.until {
+            if current > until {
+                break;
+            }
+        }
+        if let Some(count) = rule.count {
+            if emitted >= count {
+                break;
+            }
+        }
+        if current > to {
+            break;
+        }
+
+        let matches_by_day = rule.by_day.is_empty()
+            || rule.freq != Freq::Weekly
+            || rule.by_day.contains(&current.weekday());
+
+        if matches_by_day {
+            emitted += 1;
+            if current >= from && current <= to {
+                out.push(current);
+            }
+        }
+
+        current_naive = match rule.freq {
+            Freq::Daily => current_naive + Duration::days(rule.interval as i64),
+            Freq::Weekly => {
+                if rule.by_day.is_empty() {
+                    current_naive + Duration::weeks(rule.interval as i64)
+                } else {
+                    // Step a day at a time within the week; jump by
+                    // `interval` weeks once we wrap past Sunday.
+                    let next = current_naive + Duration::days(1);
+                    if next.weekday() == Weekday::Mon && rule.interval > 1 {
+                        next + Duration::weeks((rule.interval - 1) as i64)
+                    } else {
+                        next
+                    }
+                }
+            }
+            Freq::Monthly => add_months(current_naive, rule.interval as i32),
+            Freq::Yearly => add_months(current_naive, 12 * rule.interval as i32),
+        };
+
+        // Cap the number of matching candidates walked; candidates that
+        // BYDAY rejects are not counted.
+        if emitted as i64 + out.len() as i64 > 100_000 {
+            break;
+        }
+    }
+
+    out
+}
+
+/// Resolve a wall-clock `NaiveDateTime` to a concrete local instant.
+/// Ambiguous times (a DST "fall back" repeating an hour) resolve to the
+/// earlier instant; times that don't exist at all (a "spring forward"
+/// gap) fall back to treating the wall-clock value as if it were already
+/// UTC rather than panicking.
+fn naive_to_local(naive: chrono::NaiveDateTime) -> DateTime<Local> {
+    match Local.from_local_datetime(&naive) {
+        chrono::LocalResult::Single(dt) => dt,
+        chrono::LocalResult::Ambiguous(earliest, _latest) => earliest,
+        chrono::LocalResult::None => Local.from_utc_datetime(&naive),
+    }
+}
+
+fn add_months(dt: chrono::NaiveDateTime, months: i32) -> chrono::NaiveDateTime {
+    let date = dt.date();
+    let total = date.year() * 12 + date.month0() as i32 + months;
+    let year = total.div_euclid(12);
+    let month0 = total.rem_euclid(12) as u32;
+    let day = date.day();
+    // Clamp to the last valid day of the target month (e.g. Jan 31 + 1
+    // month -> Feb 28/29) rather than overflowing into the next month.
+    for day in (1..=day).rev() {
+        if let Some(naive_date) = chrono::NaiveDate::from_ymd_opt(year, month0 + 1, day) {
+            return naive_date.and_time(dt.time());
+        }
+    }
+    dt
+}
+
+fn parse_event_datetime(dt: &EventDateTime) -> Result<DateTime<Local>> {
+    dt.to_local()
+}
+
+fn to_event_datetime(dt: DateTime<Local>, time_zone: Option<String>) -> EventDateTime {
+    EventDateTime {
+        date_time: Some(dt.to_rfc3339_opts(chrono::SecondsFormat::Secs, false)),
+        date: None,
+        time_zone,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Timelike;
+
+    fn master(start: &str, end: &str, recurrence: Vec<&str>) -> Event {
+        Event {
+            id: Some("master-1".into()),
+            summary: "Standup".into(),
+            description: None,
+            start: EventDateTime {
+                date_time: Some(start.into()),
+                date: None,
+                time_zone: None,
+            },
+            end: EventDateTime {
+                date_time: Some(end.into()),
+                date: None,
+                time_zone: None,
+            },
+            location: None,
+            attendees: None,
+            recurrence: Some(recurrence.into_iter().map(String::from).collect()),
+            recurrence_id: None,
+            html_link: None,
+        }
+    }
+
+    fn window() -> (DateTime<Local>, DateTime<Local>) {
+        (
+            parse_ical_datetime("20260101T000000Z").unwrap(),
+            parse_ical_datetime("20260401T000000Z").unwrap(),
+        )
+    }
+
+    #[test]
+    fn count_terminates_after_n_occurrences() {
+        let event = master(
+            "2026-01-05T09:00:00+00:00",
+            "2026-01-05T09:30:00+00:00",
+            vec!["RRULE:FREQ=DAILY;COUNT=3"],
+        );
+        let (start, end) = window();
+        let out = expand(vec![event], start, end).unwrap();
+        assert_eq!(out.len(), 3);
+    }
+
+    #[test]
+    fn until_terminates_at_the_given_instant() {
+        let event = master(
+            "2026-01-05T09:00:00+00:00",
+            "2026-01-05T09:30:00+00:00",
+            vec!["RRULE:FREQ=DAILY;UNTIL=20260108T090000Z"],
+        );
+        let (start, end) = window();
+        let out = expand(vec![event], start, end).unwrap();
+        // Jan 5, 6, 7, 8 inclusive.
+        assert_eq!(out.len(), 4);
+    }
+
+    #[test]
+    fn byday_filters_a_weekly_series() {
+        // 2026-01-06 is a Tuesday.
+        let event = master(
+            "2026-01-06T09:00:00+00:00",
+            "2026-01-06T09:30:00+00:00",
+            vec!["RRULE:FREQ=WEEKLY;BYDAY=TU,TH;COUNT=4"],
+        );
+        let (start, end) = window();
+        let out = expand(vec![event], start, end).unwrap();
+        assert_eq!(out.len(), 4);
+        for instance in &out {
+            let weekday = instance.start.to_local().unwrap().weekday();
+            assert!(weekday == Weekday::Tue || weekday == Weekday::Thu);
+        }
+    }
+
+    #[test]
+    fn exdate_removes_the_named_occurrence() {
+        let event = master(
+            "2026-01-05T09:00:00+00:00",
+            "2026-01-05T09:30:00+00:00",
+            vec![
+                "RRULE:FREQ=DAILY;COUNT=3",
+                "EXDATE:20260106T090000Z",
+            ],
+        );
+        let (start, end) = window();
+        let out = expand(vec![event], start, end).unwrap();
+        assert_eq!(out.len(), 2);
+        for instance in &out {
+            let day = instance.start.to_local().unwrap().day();
+            assert_ne!(day, 6);
+        }
+    }
+
+    #[test]
+    fn daily_series_keeps_wall_clock_time_across_a_dst_transition() {
+        // Stepping via `Duration::days` on a `DateTime<Local>` (absolute
+        // time) would shift this 09:00 series by an hour once the zone's
+        // offset changes; stepping on the wall-clock `NaiveDateTime`
+        // keeps every occurrence at 09:00 local, which is what a human
+        // means by "every day at 9am".
+        std::env::set_var("TZ", "America/New_York");
+
+        // 2026-03-08 is when US clocks spring forward.
+        let event = master(
+            "2026-03-05T09:00:00-05:00",
+            "2026-03-05T09:30:00-05:00",
+            vec!["RRULE:FREQ=DAILY;COUNT=6"],
+        );
+        let start = parse_ical_datetime("20260301T000000Z").unwrap();
+        let end = parse_ical_datetime("20260401T000000Z").unwrap();
+        let out = expand(vec![event], start, end).unwrap();
+
+        assert_eq!(out.len(), 6);
+        for instance in &out {
+            let local = instance.start.to_local().unwrap();
+            assert_eq!(local.hour(), 9, "occurrence on {local} drifted off 09:00");
+            assert_eq!(local.minute(), 0);
+        }
+
+        std::env::remove_var("TZ");
+    }
+}
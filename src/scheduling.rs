@@ -0,0 +1,357 @@
+//! Interval-scheduling engine behind `calendar.free_slots`.
+//!
+//! Busy time is computed once as a merged, half-open `[start, end)`
+//! interval set in the user's local zone (so DST transitions fall out of
+//! `chrono`'s local-time arithmetic rather than needing special-casing),
+//! then each day in the window has its working hours punched full of
+//! holes by whichever busy intervals overlap it.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, TimeZone, Weekday};
+
+use crate::model::Event;
+
+/// A single candidate meeting slot.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Slot {
+    pub start: String,
+    pub end: String,
+    pub duration_minutes: i64,
+}
+
+/// Working-hours configuration for the search.
+pub struct WorkingHours {
+    pub start: (u32, u32),
+    pub end: (u32, u32),
+    pub days: Vec<Weekday>,
+    pub lunch: Option<((u32, u32), (u32, u32))>,
+}
+
+impl WorkingHours {
+    pub fn parse(
+        work_start: Option<&str>,
+        work_end: Option<&str>,
+        work_days: Option<&[String]>,
+        lunch_start: Option<&str>,
+        lunch_end: Option<&str>,
+    ) -> Result<Self> {
+        let start = parse_hhmm(work_start.unwrap_or("09:00"))?;
+        let end = parse_hhmm(work_end.unwrap_or("17:00"))?;
+        let days = match work_days {
+            Some(names) => names
+                .iter()
+                .map(|n| parse_weekday(n))
+                .collect::<Result<Vec<_>>>()?,
+            None => vec![
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+            ],
+        };
+        let lunch = match (lunch_start, lunch_end) {
+            (Some(s), Some(e)) => Some((parse_hhmm(s)?, parse_hhmm(e)?)),
+            _ => None,
+        };
+        Ok(Self {
+            start,
+            end,
+            days,
+            lunch,
+        })
+    }
+}
+
+fn parse_hhmm(s: &str) -> Result<(u32, u32)> {
+    let (h, m) = s
+        .split_once(':')
+        .with_context(|| format!("invalid HH:MM time: {s}"))?;
+    Ok((
+        h.parse().with_context(|| format!("invalid hour in {s}"))?,
+        m.parse().with_context(|| format!("invalid minute in {s}"))?,
+    ))
+}
+
+fn parse_weekday(s: &str) -> Result<Weekday> {
+    match s.to_uppercase().as_str() {
+        "MON" => Ok(Weekday::Mon),
+        "TUE" => Ok(Weekday::Tue),
+        "WED" => Ok(Weekday::Wed),
+        "THU" => Ok(Weekday::Thu),
+        "FRI" => Ok(Weekday::Fri),
+        "SAT" => Ok(Weekday::Sat),
+        "SUN" => Ok(Weekday::Sun),
+        other => anyhow::bail!("invalid work_days entry: {other} (expected MON..SUN)"),
+    }
+}
+
+/// Turn a local date + hour/minute into a concrete local `DateTime`,
+/// falling back to the earliest valid instant for times that don't exist
+/// because of a DST transition (e.g. the skipped hour in a "spring
+/// forward" change).
+fn local_at(date: NaiveDate, hm: (u32, u32)) -> DateTime<Local> {
+    let mut naive = date.and_hms_opt(hm.0, hm.1, 0).unwrap();
+    loop {
+        if let Some(dt) = Local.from_local_datetime(&naive).earliest() {
+            return dt;
+        }
+        // Inside a "spring forward" gap: move to the next wall-clock
+        // minute until we're past it.
+        naive += Duration::minutes(1);
+    }
+}
+
+/// Convert events (already recurrence-expanded) into merged, sorted busy
+/// `[start, end)` intervals. All-day events block the entire local day.
+fn busy_intervals(events: &[Event]) -> Vec<(DateTime<Local>, DateTime<Local>)> {
+    let mut intervals = Vec::new();
+    for event in events {
+        let (start, end) = if event.start.date.is_some() {
+            let Ok(start) = event.start.to_local() else {
+                continue;
+            };
+            (start.date_naive(), start.date_naive() + Duration::days(1))
+        } else {
+            let (Ok(start), Ok(end)) = (event.start.to_local(), event.end.to_local()) else {
+                continue;
+            };
+            intervals.push((start, end));
+            continue;
+        };
+        intervals.push((
+            local_at(start, (0, 0)),
+            local_at(end, (0, 0)),
+        ));
+    }
+
+    intervals.sort_by_key(|(start, _)| *start);
+
+    let mut merged: Vec<(DateTime<Local>, DateTime<Local>)> = Vec::new();
+    for (start, end) in intervals {
+        match merged.last_mut() {
+            Some((_, last_end)) if start <= *last_end => {
+                if end > *last_end {
+                    *last_end = end;
+                }
+            }
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+/// Subtract `busy` from every interval in `free`, splitting as needed.
+fn subtract(free: Vec<(DateTime<Local>, DateTime<Local>)>, busy: (DateTime<Local>, DateTime<Local>)) -> Vec<(DateTime<Local>, DateTime<Local>)> {
+    let mut out = Vec::new();
+    for (start, end) in free {
+        if busy.1 <= start || busy.0 >= end {
+            out.push((start, end));
+            continue;
+        }
+        if busy.0 > start {
+            out.push((start, busy.0));
+        }
+        if busy.1 < end {
+            out.push((busy.1, end));
+        }
+    }
+    out
+}
+
+/// Snap `dt` forward to the next multiple of `granularity_minutes` past
+/// local midnight.
+fn snap_up(dt: DateTime<Local>, granularity_minutes: i64) -> DateTime<Local> {
+    if granularity_minutes <= 0 {
+        return dt;
+    }
+    let midnight = local_at(dt.date_naive(), (0, 0));
+    let minutes_since_midnight = (dt - midnight).num_minutes();
+    let remainder = minutes_since_midnight % granularity_minutes;
+    if remainder == 0 {
+        dt
+    } else {
+        dt + Duration::minutes(granularity_minutes - remainder)
+    }
+}
+
+/// Search `[window_start, window_end]` for free slots at least
+/// `duration_minutes` long, respecting `hours`, snapping starts to
+/// `granularity_minutes`, and stopping once `max_results` slots are found.
+pub fn find_slots(
+    events: &[Event],
+    window_start: DateTime<Local>,
+    window_end: DateTime<Local>,
+    duration_minutes: i64,
+    hours: &WorkingHours,
+    granularity_minutes: i64,
+    max_results: usize,
+) -> Vec<Slot> {
+    let busy = busy_intervals(events);
+    let mut slots = Vec::new();
+
+    let mut day = window_start.date_naive();
+    while local_at(day, (0, 0)) < window_end && slots.len() < max_results {
+        if hours.days.contains(&day.weekday()) {
+            let day_start = local_at(day, hours.start).max(window_start);
+            let day_end = local_at(day, hours.end).min(window_end);
+
+            if day_start < day_end {
+                let mut free = vec![(day_start, day_end)];
+                if let Some((lunch_start, lunch_end)) = hours.lunch {
+                    free = subtract(free, (local_at(day, lunch_start), local_at(day, lunch_end)));
+                }
+                for b in &busy {
+                    if b.1 > day_start && b.0 < day_end {
+                        free = subtract(free, *b);
+                    }
+                }
+
+                for (start, end) in free {
+                    let snapped = snap_up(start, granularity_minutes);
+                    let length = end - snapped;
+                    if length >= Duration::minutes(duration_minutes) {
+                        slots.push(Slot {
+                            start: snapped.to_rfc3339_opts(chrono::SecondsFormat::Secs, false),
+                            end: end.to_rfc3339_opts(chrono::SecondsFormat::Secs, false),
+                            duration_minutes: length.num_minutes(),
+                        });
+                        if slots.len() >= max_results {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+        day += Duration::days(1);
+    }
+
+    slots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::EventDateTime;
+
+    /// Build a timed busy event on 2026-01-05 at the given hour:minute
+    /// boundaries, going through `local_at`/`to_rfc3339` so the resulting
+    /// `dateTime` round-trips through `EventDateTime::to_local` correctly
+    /// regardless of the system's time zone.
+    fn timed_event(start_hm: (u32, u32), end_hm: (u32, u32)) -> Event {
+        let day = NaiveDate::from_ymd_opt(2026, 1, 5).unwrap();
+        let rfc3339 = |hm: (u32, u32)| {
+            local_at(day, hm).to_rfc3339_opts(chrono::SecondsFormat::Secs, false)
+        };
+        Event {
+            id: Some("busy-1".into()),
+            summary: "Busy".into(),
+            description: None,
+            start: EventDateTime {
+                date_time: Some(rfc3339(start_hm)),
+                date: None,
+                time_zone: None,
+            },
+            end: EventDateTime {
+                date_time: Some(rfc3339(end_hm)),
+                date: None,
+                time_zone: None,
+            },
+            location: None,
+            attendees: None,
+            recurrence: None,
+            recurrence_id: None,
+            html_link: None,
+        }
+    }
+
+    fn all_day_event(date: &str) -> Event {
+        Event {
+            id: Some("all-day-1".into()),
+            summary: "Out of office".into(),
+            description: None,
+            start: EventDateTime {
+                date_time: None,
+                date: Some(date.into()),
+                time_zone: None,
+            },
+            end: EventDateTime {
+                date_time: None,
+                date: Some(date.into()),
+                time_zone: None,
+            },
+            location: None,
+            attendees: None,
+            recurrence: None,
+            recurrence_id: None,
+            html_link: None,
+        }
+    }
+
+    // 2026-01-05 is a Monday.
+    fn monday_window() -> (DateTime<Local>, DateTime<Local>) {
+        let day = NaiveDate::from_ymd_opt(2026, 1, 5).unwrap();
+        (local_at(day, (0, 0)), local_at(day, (23, 59)))
+    }
+
+    fn weekday_hours() -> WorkingHours {
+        WorkingHours {
+            start: (9, 0),
+            end: (17, 0),
+            days: vec![
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+            ],
+            lunch: None,
+        }
+    }
+
+    #[test]
+    fn free_day_yields_one_slot_spanning_working_hours() {
+        let (start, end) = monday_window();
+        let slots = find_slots(&[], start, end, 30, &weekday_hours(), 15, 20);
+        assert_eq!(slots.len(), 1);
+        assert_eq!(slots[0].duration_minutes, 8 * 60);
+    }
+
+    #[test]
+    fn a_busy_meeting_splits_the_working_day_in_two() {
+        let (start, end) = monday_window();
+        let busy = timed_event((12, 0), (13, 0));
+        let slots = find_slots(&[busy], start, end, 30, &weekday_hours(), 15, 20);
+        assert_eq!(slots.len(), 2);
+        assert_eq!(slots[0].duration_minutes, 3 * 60); // 09:00-12:00
+        assert_eq!(slots[1].duration_minutes, 4 * 60); // 13:00-17:00
+    }
+
+    #[test]
+    fn lunch_exclusion_splits_the_working_day_even_with_no_busy_events() {
+        let (start, end) = monday_window();
+        let mut hours = weekday_hours();
+        hours.lunch = Some(((12, 0), (13, 0)));
+        let slots = find_slots(&[], start, end, 30, &hours, 15, 20);
+        assert_eq!(slots.len(), 2);
+        assert_eq!(slots[0].duration_minutes, 3 * 60);
+        assert_eq!(slots[1].duration_minutes, 4 * 60);
+    }
+
+    #[test]
+    fn all_day_event_blocks_the_entire_working_day() {
+        let (start, end) = monday_window();
+        let busy = all_day_event("2026-01-05");
+        let slots = find_slots(&[busy], start, end, 30, &weekday_hours(), 15, 20);
+        assert!(slots.is_empty());
+    }
+
+    #[test]
+    fn gap_shorter_than_duration_is_dropped() {
+        let (start, end) = monday_window();
+        // Only a 20-minute gap between 16:40 and 17:00.
+        let busy = timed_event((9, 0), (16, 40));
+        let slots = find_slots(&[busy], start, end, 30, &weekday_hours(), 15, 20);
+        assert!(slots.is_empty());
+    }
+}